@@ -1,13 +1,52 @@
-use std::{env, fs};
+use std::{env, ffi::OsString, fs, io, process, thread};
 
-use parser::{Rule, ParserTrait, ExpressionParser};
+use runner::Limits;
+
+const USAGE: &str = "usage: cacau run <file>";
 
 fn main() {
-    let file = env::args_os().skip(1).next().expect("no file supplied");
+    let mut args = env::args_os().skip(1);
+
+    match (args.next(), args.next()) {
+        (Some(command), Some(file)) if command == "run" => {
+            // Calls and expressions nest on the native stack, so programs get a thread with as
+            // much of it as the limits expect
+            let limits = Limits::default();
+            thread::Builder::new()
+                .stack_size(limits.stack_size)
+                .spawn(move || run(file, limits))
+                .expect("failed to spawn the thread that runs the program")
+                .join()
+                .unwrap();
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    }
+}
 
-    let contents = fs::read_to_string(file).unwrap();
+/// Parses and executes the program at `file`, exiting with code 1 if it fails
+fn run(file: OsString, limits: Limits) {
+    let contents = match fs::read_to_string(&file) {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("failed to read {}: {}", file.to_string_lossy(), err);
+            process::exit(1);
+        }
+    };
 
-    let parsed = ExpressionParser::parse(Rule::program, &contents).expect("failed to parse");
+    let program = match parser::parse(&contents) {
+        Ok(program) => program,
+        Err(err) => {
+            eprintln!("{}", err.with_path(&file.to_string_lossy()));
+            process::exit(1);
+        }
+    };
 
-    println!("parsed: {}", parsed);
-}
\ No newline at end of file
+    let mut stdout = io::stdout();
+    if let Err(err) = runner::Runner::run_with_limits(&program, &mut stdout, limits) {
+        eprint!("{}", err.report(&file.to_string_lossy(), &contents));
+        process::exit(1);
+    }
+}
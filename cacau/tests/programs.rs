@@ -0,0 +1,171 @@
+//! Runs every `.cau` file in the `tests` folder at the root of the repository,
+//! which pass when they run to completion without errors (see `tests/README.md`).
+//!
+//! What a program prints must match the `.stdout` file next to it, e.g.
+//! `01_assignments.stdout` for `01_assignments.cau`. A missing `.stdout` file
+//! means that nothing is printed. Set `CACAU_BLESS=1` to rewrite those files
+//! with the output of every program that passes:
+//!
+//! ```text
+//! CACAU_BLESS=1 cargo test -p cacau --test programs
+//! ```
+//!
+//! Pass a substring as an argument to only run the files whose names contain it:
+//!
+//! ```text
+//! cargo test -p cacau --test programs -- precedence
+//! ```
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process, thread,
+};
+
+use runner::Limits;
+
+/// Programs that use features which aren't implemented yet.
+///
+/// They are still run, and a program in this list that passes is reported as
+/// an error, so that it's removed from here as soon as its feature lands.
+const EXPECTED_FAILURES: &[&str] = &[
+    // `print`
+    "00_hello_world.cau",
+    // `print`
+    "08_if_elif_else.cau",
+];
+
+fn main() {
+    let filter = env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let bless = env::var_os("CACAU_BLESS").is_some();
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests");
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", dir.display(), err))
+        .map(|entry| entry.expect("failed to read directory entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "cau"))
+        .collect();
+    paths.sort();
+
+    let mut passed = 0;
+    let mut expected_failures = 0;
+    let mut errors = Vec::new();
+
+    for path in paths {
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        if filter.as_ref().is_some_and(|filter| !name.contains(filter)) {
+            continue;
+        }
+
+        let result =
+            run_on_own_thread(&path, &name).and_then(|stdout| check_stdout(&path, &stdout, bless));
+        let expected_failure = EXPECTED_FAILURES.contains(&name.as_str());
+
+        let status = match (result, expected_failure) {
+            (Ok(()), false) => {
+                passed += 1;
+                "ok"
+            }
+            (Err(_), true) => {
+                expected_failures += 1;
+                "expected failure"
+            }
+            (Ok(()), true) => {
+                errors.push(format!(
+                    "{} passed, remove it from EXPECTED_FAILURES\n",
+                    name
+                ));
+                "UNEXPECTED PASS"
+            }
+            (Err(err), false) => {
+                errors.push(err);
+                "FAILED"
+            }
+        };
+        println!("test {} ... {}", name, status);
+    }
+
+    for error in errors.iter() {
+        println!("\n{}", error);
+    }
+
+    println!(
+        "\ntest result: {}. {} passed; {} failed; {} expected failures",
+        if errors.is_empty() { "ok" } else { "FAILED" },
+        passed,
+        errors.len(),
+        expected_failures,
+    );
+
+    if !errors.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Runs the program at `path` on a thread with as much stack as `cacau run` gives it
+fn run_on_own_thread(path: &Path, name: &str) -> Result<String, String> {
+    thread::scope(|scope| {
+        thread::Builder::new()
+            .stack_size(Limits::default().stack_size)
+            .spawn_scoped(scope, || run(path, name))
+            .expect("failed to spawn the thread that runs the program")
+            .join()
+            .unwrap()
+    })
+}
+
+/// Parses and runs the program at `path`, returning what it printed or the error report if it fails
+fn run(path: &Path, name: &str) -> Result<String, String> {
+    let display_path = format!("tests/{}", name);
+    let source = fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+
+    let program = parser::parse(&source).map_err(|err| err.with_path(&display_path).to_string())?;
+
+    let mut stdout = Vec::new();
+    runner::Runner::run(&program, &mut stdout).map_err(|err| err.report(&display_path, &source))?;
+
+    String::from_utf8(stdout).map_err(|err| format!("{} printed invalid UTF-8: {}", name, err))
+}
+
+/// Compares `stdout` with the expected output of the program at `path`, or
+/// stores it as the new expectation when blessing
+fn check_stdout(path: &Path, stdout: &str, bless: bool) -> Result<(), String> {
+    let expected_path = path.with_extension("stdout");
+
+    if bless {
+        let result = if stdout.is_empty() {
+            fs::remove_file(&expected_path).or_else(|err| match err.kind() {
+                io::ErrorKind::NotFound => Ok(()),
+                _ => Err(err),
+            })
+        } else {
+            fs::write(&expected_path, stdout)
+        };
+        return result
+            .map_err(|err| format!("failed to bless {}: {}", expected_path.display(), err));
+    }
+
+    let expected = match fs::read_to_string(&expected_path) {
+        Ok(expected) => expected,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(format!(
+                "failed to read {}: {}",
+                expected_path.display(),
+                err
+            ))
+        }
+    };
+
+    if stdout == expected {
+        return Ok(());
+    }
+
+    let name = expected_path.file_name().unwrap().to_string_lossy();
+    Err(format!(
+        "stdout doesn't match tests/{} (set CACAU_BLESS=1 to update it)\n\
+         --- expected\n{}--- found\n{}",
+        name, expected, stdout
+    ))
+}
@@ -1,8 +1,11 @@
 pub use pest::Parser as ParserTrait;
 
-
 use pest_derive::Parser;
 
+mod lower;
+
+pub use lower::{lower_program, parse, Error, Result};
+
 #[derive(Parser)]
 #[grammar = "grammar.pest"]
 pub struct ExpressionParser;
@@ -48,6 +51,17 @@ mod parser_tests {
         assert_does_not_parse(Rule::identifier, "if");
         assert_does_not_parse(Rule::identifier, "then");
         assert_does_not_parse(Rule::identifier, "else");
+        assert_does_not_parse(Rule::identifier, "xor");
+        assert_does_not_parse(Rule::identifier, "nand");
+        assert_does_not_parse(Rule::identifier, "nor");
+
+        // But identifiers may start with one
+        assert_parses(Rule::identifier, "nothing");
+        assert_parses(Rule::identifier, "letter");
+        assert_parses(Rule::identifier, "iffy");
+        assert_parses(Rule::identifier, "xor_result");
+        assert_parses(Rule::identifier, "north");
+        assert_parses(Rule::program, "let letter = nothing");
     }
 
     // TODO: this test is failing ;-;
@@ -119,6 +133,48 @@ mod parser_tests {
         assert_does_not_parse(Rule::assignment, "six: BigDecimal = 10");
     }
 
+    #[test]
+    fn reassignment() {
+        assert_parses(Rule::reassignment, "a = a + 10");
+        assert_parses(Rule::reassignment, "i += 1");
+        assert_parses(Rule::reassignment, "i -= 1");
+        assert_parses(Rule::reassignment, "area *= 2");
+        assert_parses(Rule::reassignment, "area /= 2");
+        assert_parses(Rule::reassignment, "i %= 2");
+        assert_parses(Rule::reassignment, "i ^= 2");
+        assert_parses(Rule::reassignment, "text += \"oi\"");
+
+        assert_does_not_parse(Rule::reassignment, "a == b");
+        assert_does_not_parse(Rule::reassignment, "a =");
+        assert_does_not_parse(Rule::reassignment, "let a = 1");
+        assert_does_not_parse(Rule::reassignment, "2 = 1");
+
+        assert_parses(Rule::reassignment, "(a, b) = (b, a)");
+        assert_parses(Rule::reassignment, "(a, (b, c),) = t");
+        assert_does_not_parse(Rule::reassignment, "(a) = t");
+        assert_does_not_parse(Rule::reassignment, "(a, 2) = t");
+    }
+
+    #[test]
+    fn tuples() {
+        assert_parses(Rule::parenthesized, "(10, 20)");
+        assert_parses(Rule::parenthesized, "(10, 20,)");
+        assert_parses(Rule::parenthesized, "(\"one\",)");
+        assert_parses(Rule::parenthesized, "((1, 2), [3])");
+        assert_parses(Rule::operation, "(1, 2) == (1, 2)");
+        assert_parses(Rule::operation, "tuple.get(-1)");
+
+        assert_does_not_parse(Rule::parenthesized, "()");
+        assert_does_not_parse(Rule::parenthesized, "(,)");
+        assert_does_not_parse(Rule::parenthesized, "(1,,)");
+
+        assert_parses(Rule::assignment, "let (a, b) = (10, 20)");
+        assert_parses(Rule::assignment, "let (a, (b, c)) = t");
+        assert_parses(Rule::assignment, "let (a,) = t");
+        assert_does_not_parse(Rule::assignment, "let (a) = t");
+        assert_does_not_parse(Rule::assignment, "let () = t");
+    }
+
     #[test]
     fn function_argument() {
         assert_parses(Rule::function_argument, "x: int");
@@ -167,6 +223,14 @@ mod parser_tests {
             "fn several_args_with_return x: int, y: int, z: int -> bool",
         );
 
+        assert_parses(Rule::function_declaration, "fn vaccinate self -> Self");
+        assert_parses(Rule::function_declaration, "fn grow self, years: int");
+        assert_parses(
+            Rule::function_declaration,
+            "fn grow self, years: int, -> int",
+        );
+        assert_parses(Rule::function_declaration, "fn selfish x: int");
+
         assert_does_not_parse(Rule::function_declaration, "fn");
         assert_does_not_parse(Rule::function_declaration, "pub fn");
         assert_does_not_parse(Rule::function_declaration, "fn -> bool");
@@ -223,22 +287,33 @@ mod parser_tests {
 
     #[test]
     fn boolean_operations() {
-        assert_parses(Rule::boolean_expr, "true and false");
-        assert_parses(Rule::boolean_expr, "false or not true");
-        assert_parses(Rule::boolean_expr, "false or (not (true or false))");
-        assert_parses(Rule::boolean_expr, "false or (false and true)");
-        assert_parses(Rule::boolean_expr, "false or (false and (true or false))");
-        assert_parses(Rule::boolean_expr, "not true");
+        assert_parses(Rule::operation, "true and false");
+        assert_parses(Rule::operation, "false or not true");
+        assert_parses(Rule::operation, "false or (not (true or false))");
+        assert_parses(Rule::operation, "false or (false and true)");
+        assert_parses(Rule::operation, "false or (false and (true or false))");
+        assert_parses(Rule::operation, "not true");
         assert_parses(
-            Rule::boolean_expr,
+            Rule::operation,
             "false or (false and (true or (true and false)))",
         );
         assert_parses(
-            Rule::boolean_expr,
+            Rule::operation,
             "(false and (true or (true and false))) or (false and (true or (true and (true))))",
         );
     }
 
+    #[test]
+    fn bitwise_operations() {
+        assert_parses(Rule::operation, "a & b | c");
+        assert_parses(Rule::operation, "true & (false | true) == true");
+        assert_parses(Rule::operation, "1 << 4 >> 2");
+        assert_parses(Rule::operation, "~0 == -1");
+        assert_parses(Rule::operation, "~~a & ~(b | c)");
+        assert_parses(Rule::operation, "12 ~ 10 == 6");
+        assert_parses(Rule::operation, "a ~ ~b");
+    }
+
     #[test]
     fn enum_definition() {
         assert_parses(
@@ -248,6 +323,10 @@ mod parser_tests {
         assert_parses(Rule::enum_definition, "pub enum Status { Polling, Ready }");
         assert_parses(Rule::enum_definition, "pub enum Status { Polling, Ready, }");
         assert_parses(Rule::enum_definition, "pub enum NoVariant { }");
+        assert_parses(
+            Rule::enum_definition,
+            "enum Inventory { Coins(list<int>), Empty }",
+        );
 
         assert_does_not_parse(Rule::enum_definition, "pub enum");
 
@@ -276,19 +355,128 @@ mod parser_tests {
             Rule::struct_definition,
             "pub struct User { pub username: , age: int, birth_date: Date }",
         );
+
+        // Generic field types
+        assert_parses(
+            Rule::struct_definition,
+            "struct Wallet { coins: list<int>, owners: map<string, list<User>>, }",
+        );
+        assert_does_not_parse(Rule::struct_definition, "struct Wallet { coins: list< }");
+    }
+
+    #[test]
+    fn impl_block() {
+        assert_parses(Rule::impl_block, "impl Person { }");
+        assert_parses(
+            Rule::impl_block,
+            "impl Person { fn vaccinate self -> Self { self.is_vaccinated = true; } fn zero -> int { 0 } }",
+        );
+        assert_parses(
+            Rule::program,
+            "impl Person { fn age self -> int { self.age } }",
+        );
+
+        assert_does_not_parse(Rule::impl_block, "impl { }");
+        assert_does_not_parse(Rule::impl_block, "impl Person { let x = 1 }");
+        // `self` can only come first
+        assert_does_not_parse(
+            Rule::program,
+            "impl Person { fn grow years: int, self { } }",
+        );
+        assert_does_not_parse(Rule::identifier, "impl");
+    }
+
+    #[test]
+    fn struct_literal() {
+        assert_parses(Rule::struct_literal, "User { }");
+        assert_parses(Rule::struct_literal, "Point { x: 1, y: 2 }");
+        assert_parses(Rule::struct_literal, "Point { x: 1 + 1, y: f(2), }");
+        assert_parses(Rule::operation, "Point { x: 1, y: 2 }.x");
+        assert_parses(Rule::operation, "joao.age + 1");
+        assert_parses(Rule::reassignment, "joao.is_vaccinated = true");
+        assert_parses(Rule::reassignment, "a.b.c += 1");
+
+        // The name of the struct must start with an uppercase letter
+        assert_does_not_parse(Rule::struct_literal, "point { x: 1 }");
+        assert_does_not_parse(Rule::struct_literal, "Point { x }");
+        assert_does_not_parse(Rule::struct_literal, "Point { x: 1 y: 2 }");
+    }
+
+    #[test]
+    fn variant() {
+        assert_parses(Rule::variant, "User::Admin(data)");
+        assert_parses(Rule::variant, "User::Admin(UserData { name: \"joao\" })");
+        assert_parses(Rule::variant, "Status::Ready");
+        assert_parses(Rule::operation, "Status::Ready == status");
+        assert_parses(Rule::operation, "Status::Ready.is_ready()");
+        // A variant holds a single value, which may be a tuple
+        assert_parses(Rule::variant, "Point::Cartesian((1, 2))");
+        // Functions of a type that don't take `self` are called the same way
+        assert_parses(Rule::variant, "Point::new(1, 2)");
+        assert_parses(Rule::variant, "Point::origin()");
+
+        assert_does_not_parse(Rule::variant, "User::(data)");
+        assert_does_not_parse(Rule::variant, "::Admin");
+    }
+
+    #[test]
+    fn lambda() {
+        assert_parses(Rule::lambda, "fn a, b { a + b }");
+        assert_parses(Rule::lambda, "fn x { x * 2 }");
+        assert_parses(Rule::lambda, "fn { println(1) }");
+        assert_parses(Rule::assignment, "let sum = fn a, b { a + b }");
+        assert_parses(Rule::function_call, "apply(fn x { x }, 2)");
+        // `fn` names the type of functions
+        assert_parses(Rule::function_declaration, "fn make_adder n: int -> fn");
+
+        assert_does_not_parse(Rule::lambda, "fn a b { a }");
+        assert_does_not_parse(Rule::lambda, "fn x");
+        assert_does_not_parse(Rule::lambda, "fnx { x }");
+    }
+
+    #[test]
+    fn match_expr() {
+        assert_parses(Rule::match_expr, "match x { _ => 0 }");
+        assert_parses(
+            Rule::match_expr,
+            "match x { 1 => \"one\", -1 => \"minus one\", _ => \"other\", }",
+        );
+        assert_parses(
+            Rule::match_expr,
+            "match user { User::Admin(data) if data.active => { grant(data) } Guest => 0, }",
+        );
+        assert_parses(Rule::match_expr, "match pair { (0, y) => y, (x, _) => x }");
+        assert_parses(
+            Rule::match_expr,
+            "match p { Point { x: 0, y } => y, Point { x, .. } => x }",
+        );
+        assert_parses(
+            Rule::match_expr,
+            "match opt { Some(Some(x)) => x, Some(None) => 0, None => 0 }",
+        );
+        assert_parses(Rule::operation, "match x { _ => 1 } + 1");
+        assert_parses(Rule::match_pattern, "trueish");
+        assert_parses(Rule::match_pattern, "'a'");
+
+        // Arms other than blocks are separated by commas
+        assert_does_not_parse(Rule::match_expr, "match x { 1 => 1 _ => 0 }");
+        assert_does_not_parse(Rule::match_expr, "match x { 1 => }");
+        assert_does_not_parse(Rule::match_expr, "match { _ => 0 }");
+        // `..` must come last
+        assert_does_not_parse(Rule::match_expr, "match p { Point { .., x } => x }");
     }
 
     #[test]
-    fn math_op() {
-        assert_parses(Rule::math_expr, "2");
-        assert_parses(Rule::math_expr, "-2");
-        assert_parses(Rule::math_expr, "-2 * 5");
-        assert_parses(Rule::math_expr, "-(2*5)");
-        assert_parses(Rule::math_expr, "x + y");
-        assert_parses(Rule::math_expr, "2 + 2");
-        assert_parses(Rule::math_expr, "2 - (2*3)");
-        assert_parses(Rule::math_expr, "(2^3)/2");
-        assert_parses(Rule::math_expr, "(2-y) * (3+6)");
+    fn arithmetic_operations() {
+        assert_parses(Rule::operation, "2");
+        assert_parses(Rule::operation, "-2");
+        assert_parses(Rule::operation, "-2 * 5");
+        assert_parses(Rule::operation, "-(2*5)");
+        assert_parses(Rule::operation, "x + y");
+        assert_parses(Rule::operation, "2 + 2");
+        assert_parses(Rule::operation, "2 - (2*3)");
+        assert_parses(Rule::operation, "(2^3)/2");
+        assert_parses(Rule::operation, "(2-y) * (3+6)");
 
         assert_parses(Rule::expression, "2");
         assert_parses(Rule::expression, "2 + 2");
@@ -297,11 +485,11 @@ mod parser_tests {
         assert_parses(Rule::expression, "(2-2) * (3+6)");
         assert_parses(Rule::expression, "(2-2) % (3+6)");
 
-        assert_does_not_parse(Rule::math_expr, "-");
-        assert_does_not_parse(Rule::math_expr, "()");
+        assert_does_not_parse(Rule::operation, "-");
+        assert_does_not_parse(Rule::operation, "()");
 
         assert_does_not_parse(Rule::expression, "()");
-        assert_does_not_parse(Rule::math_expr, "*3");
+        assert_does_not_parse(Rule::operation, "*3");
     }
 
     #[test]
@@ -318,7 +506,8 @@ mod parser_tests {
             "pub fn double x: int, b: bool, s: str -> SomeType { 2 * (2-3) ^ 5 }",
         );
 
-        assert_does_not_parse(
+        // Operand types are only checked when running
+        assert_parses(
             Rule::function_definition,
             "pub fn double x: int, b: bool, s: str -> SomeType { 2 * (2-3) ^ true }",
         );
@@ -344,14 +533,59 @@ mod parser_tests {
     #[test]
     fn elif() {
         assert_parses(Rule::if_expr, "if some_condition { 2*2 }");
-        assert_parses(Rule::if_expr, "if some_condition { 2*2 } elif another_condition { 4* 4} ");
-        assert_parses(Rule::if_expr, "if some_condition { 2*2 } elif another_condition { 4* 4} else { 6*6 } ");
-        
+        assert_parses(
+            Rule::if_expr,
+            "if some_condition { 2*2 } elif another_condition { 4* 4} ",
+        );
+        assert_parses(
+            Rule::if_expr,
+            "if some_condition { 2*2 } elif another_condition { 4* 4} else { 6*6 } ",
+        );
+
         assert_does_not_parse(Rule::if_expr, "if some_condition ");
-        assert_does_not_parse(Rule::if_expr, "if some_condition  elif another_condition { 4* 4} ");
-        assert_does_not_parse(Rule::program, "if some_condition { 2*2 } elif another_condition { 4* 4} else ");
+        assert_does_not_parse(
+            Rule::if_expr,
+            "if some_condition  elif another_condition { 4* 4} ",
+        );
+        assert_does_not_parse(
+            Rule::program,
+            "if some_condition { 2*2 } elif another_condition { 4* 4} else ",
+        );
     }
 
+    #[test]
+    fn list() {
+        assert_parses(Rule::list, "[]");
+        assert_parses(Rule::list, "[5, 10, 15]");
+        assert_parses(Rule::list, "[5, 10, 15,]");
+        assert_parses(Rule::list, "[[1], [], [2, 3]]");
+        assert_parses(Rule::operation, "lista[-1] + lista[0]");
+        assert_parses(Rule::operation, "matrix[1][2]");
+        assert_parses(Rule::operation, "[1, 2][0]");
+
+        assert_does_not_parse(Rule::list, "[,]");
+        assert_does_not_parse(Rule::program, "[1, 2");
+        assert_does_not_parse(Rule::index, "[]");
+    }
+
+    #[test]
+    fn block() {
+        assert_parses(Rule::block, "{}");
+        assert_parses(Rule::block, "{ 2 }");
+        assert_parses(Rule::block, "{ println(i); count_up_to_n(i + 1, n); }");
+        assert_parses(Rule::block, "{ let x = 2; { let y = x; y } }");
+        assert_parses(
+            Rule::function_definition,
+            "fn count_from_zero_to_ten { count_up_to_n(0, 10); }",
+        );
+        assert_parses(
+            Rule::if_expr,
+            "if some_condition { println(1); 2 } else { println(3); 4 }",
+        );
+
+        assert_does_not_parse(Rule::block, "{ println(1);");
+        assert_does_not_parse(Rule::block, "{ ; }");
+    }
 
     #[test]
     fn program() {
@@ -379,27 +613,28 @@ mod parser_tests {
         "###;
 
         assert_parses(Rule::program, valid_program);
+        assert_parses(Rule::program, "");
+        assert_parses(Rule::program, "// Only a comment");
         assert_does_not_parse(Rule::program, missing_else_expr);
     }
 
     #[test]
     fn comparison() {
-        assert_parses(Rule::comparison, "x == y");
-        assert_parses(Rule::comparison, "2 != 3");
-        assert_parses(Rule::comparison, "x != y");
-        assert_parses(Rule::comparison, "x >= y");
-        assert_parses(Rule::comparison, "x <= y");
-        assert_parses(Rule::comparison, "x < y");
-        assert_parses(Rule::comparison, "x > y");
-        assert_parses(Rule::comparison, "\"abc\" > \"oop\"");
-
-        assert_does_not_parse(Rule::comparison, "x>");
-        assert_does_not_parse(Rule::comparison, "y<=");
-        assert_does_not_parse(Rule::comparison, "==");
-        assert_does_not_parse(Rule::comparison, "<= z");
+        assert_parses(Rule::operation, "x == y");
+        assert_parses(Rule::operation, "2 != 3");
+        assert_parses(Rule::operation, "x != y");
+        assert_parses(Rule::operation, "x >= y");
+        assert_parses(Rule::operation, "x <= y");
+        assert_parses(Rule::operation, "x < y");
+        assert_parses(Rule::operation, "x > y");
+        assert_parses(Rule::operation, "\"abc\" > \"oop\"");
+
+        assert_does_not_parse(Rule::program, "x>");
+        assert_does_not_parse(Rule::program, "y<=");
+        assert_does_not_parse(Rule::program, "==");
+        assert_does_not_parse(Rule::program, "<= z");
     }
 
-
     #[test]
     fn function_calls() {
         assert_parses(Rule::function_call, "print()");
@@ -407,7 +642,6 @@ mod parser_tests {
         assert_parses(Rule::function_call, "println(\"haha\", 'c', 2, 2*2)");
         assert_parses(Rule::function_call, "println(double)");
 
-
         assert_does_not_parse(Rule::function_call, "(\"haha\", 'c', 2, 2*2)");
     }
 }
@@ -0,0 +1,1383 @@
+//! Lowering of the pest parse tree into the runner's AST.
+
+use std::{borrow::Cow, fmt, iter::Peekable};
+
+use pest::{
+    error::ErrorVariant,
+    iterators::{Pair, Pairs},
+    Parser, Span,
+};
+use runner::ast::{
+    ArithmeticOperation, ArithmeticOperator, Assignee, Assignment, BitwiseOperation,
+    BitwiseOperator, Block, BooleanOperation, BooleanOperator, CacauProgram, ComparisonOperation,
+    ComparisonOperator, Elif, Enum, Expression, ExpressionKind, FieldAccess, FieldPattern,
+    FieldValue, Function, FunctionArgument, FunctionCall, HighLevelItem, If, Impl, Index, Lambda,
+    Match, MatchArm, MethodCall, Pattern, Reassignment, Span as AstSpan, Struct, StructField,
+    StructLiteral, StructPattern, Variant, VariantLiteral, VariantPattern,
+};
+
+use crate::{ExpressionParser, Rule};
+
+/// An error found while parsing or lowering a Cacau program
+#[derive(Debug)]
+pub struct Error(Box<pest::error::Error<Rule>>);
+
+impl Error {
+    fn custom(span: Span, message: impl Into<String>) -> Self {
+        let variant = ErrorVariant::CustomError {
+            message: message.into(),
+        };
+
+        Self(Box::new(pest::error::Error::new_from_span(variant, span)))
+    }
+
+    /// Shows `path` as the file name when the error is displayed
+    pub fn with_path(self, path: &str) -> Self {
+        Self(Box::new(self.0.with_path(path)))
+    }
+}
+
+impl From<pest::error::Error<Rule>> for Error {
+    fn from(error: pest::error::Error<Rule>) -> Self {
+        Self(Box::new(error))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Parses `source` and lowers it into a [`CacauProgram`]
+pub fn parse(source: &str) -> Result<CacauProgram<'_>> {
+    let pairs = ExpressionParser::parse(Rule::program, source)?;
+
+    lower_program(pairs)
+}
+
+/// Lowers the pairs produced by parsing [`Rule::program`]
+pub fn lower_program(pairs: Pairs<'_, Rule>) -> Result<CacauProgram<'_>> {
+    let mut items = Vec::new();
+
+    for pair in pairs {
+        let item = match pair.as_rule() {
+            Rule::EOI => break,
+            Rule::semicolon => continue,
+            Rule::function_definition => HighLevelItem::Fn(lower_function(pair)?),
+            Rule::struct_definition => HighLevelItem::Struct(lower_struct(pair)),
+            Rule::enum_definition => HighLevelItem::Enum(lower_enum(pair)),
+            Rule::impl_block => HighLevelItem::Impl(lower_impl(pair)?),
+            _ => HighLevelItem::Expr(lower_expression(pair)?),
+        };
+        items.push(item);
+    }
+
+    Ok(CacauProgram { items })
+}
+
+fn lower_function(pair: Pair<'_, Rule>) -> Result<Function<'_>> {
+    let span = span_of(&pair);
+    let mut pairs = pair.into_inner();
+    let declaration = pairs.next().expect("function without declaration");
+    let body = lower_expression(pairs.next().expect("function without body"))?;
+
+    let mut public = false;
+    let mut name = "";
+    let mut receiver = false;
+    let mut params = Vec::new();
+    let mut output = None;
+
+    for pair in declaration.into_inner() {
+        match pair.as_rule() {
+            Rule::pub_ => public = true,
+            Rule::identifier => name = pair.as_str(),
+            Rule::self_param => receiver = true,
+            Rule::function_argument => {
+                let span = span_of(&pair);
+                let mut pairs = pair.into_inner();
+                let name = pairs.next().expect("argument without name").as_str();
+                let type_ = lower_type_annotation(pairs.next().expect("argument without type"));
+                params.push(FunctionArgument { name, type_, span });
+            }
+            Rule::function_return => {
+                let type_ = pair.into_inner().last().expect("return without type");
+                output = Some(type_.as_str());
+            }
+            rule => unreachable!("unexpected {:?} in function declaration", rule),
+        }
+    }
+
+    Ok(Function {
+        public,
+        name,
+        receiver,
+        params,
+        output,
+        body,
+        span,
+    })
+}
+
+fn lower_impl(pair: Pair<'_, Rule>) -> Result<Impl<'_>> {
+    let span = span_of(&pair);
+    let mut pairs = pair
+        .into_inner()
+        .skip_while(|pair| pair.as_rule() == Rule::impl_);
+    let ty = pairs.next().expect("impl without type").as_str();
+    let methods = pairs.map(lower_function).collect::<Result<_>>()?;
+
+    Ok(Impl { ty, methods, span })
+}
+
+fn lower_struct(pair: Pair<'_, Rule>) -> Struct<'_> {
+    let span = span_of(&pair);
+    let mut public = false;
+    let mut name = "";
+    let mut fields = Vec::new();
+
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::pub_ => public = true,
+            Rule::identifier => name = pair.as_str(),
+            Rule::struct_field => fields.push(lower_struct_field(pair)),
+            rule => unreachable!("unexpected {:?} in struct definition", rule),
+        }
+    }
+
+    Struct {
+        public,
+        name,
+        fields,
+        span,
+    }
+}
+
+fn lower_struct_field(pair: Pair<'_, Rule>) -> StructField<'_> {
+    let span = span_of(&pair);
+    let mut public = false;
+    let mut name = "";
+    let mut type_ = "";
+
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::pub_ => public = true,
+            Rule::identifier => name = pair.as_str(),
+            Rule::type_annotation => type_ = lower_type_annotation(pair),
+            rule => unreachable!("unexpected {:?} in struct field", rule),
+        }
+    }
+
+    StructField {
+        public,
+        name,
+        type_,
+        span,
+    }
+}
+
+fn lower_enum(pair: Pair<'_, Rule>) -> Enum<'_> {
+    let span = span_of(&pair);
+    let mut public = false;
+    let mut name = "";
+    let mut variants = Vec::new();
+
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::pub_ => public = true,
+            Rule::identifier => name = pair.as_str(),
+            Rule::enum_variant => {
+                let span = span_of(&pair);
+                let mut pairs = pair.into_inner();
+                let name = pairs.next().expect("variant without name").as_str();
+                let payload = pairs.next().map(|payload| payload.as_str());
+                variants.push(Variant {
+                    name,
+                    payload,
+                    span,
+                });
+            }
+            rule => unreachable!("unexpected {:?} in enum definition", rule),
+        }
+    }
+
+    Enum {
+        public,
+        name,
+        variants,
+        span,
+    }
+}
+
+fn lower_type_annotation(pair: Pair<'_, Rule>) -> &str {
+    only_child(pair).as_str()
+}
+
+/// Lowers any pair that represents an expression
+fn lower_expression(pair: Pair<'_, Rule>) -> Result<Expression<'_>> {
+    let span = span_of(&pair);
+    let kind = match pair.as_rule() {
+        Rule::expression => return lower_expression(only_child(pair)),
+        Rule::operation => return lower_operation(pair),
+        Rule::parenthesized => return lower_parenthesized(pair),
+        Rule::identifier => ExpressionKind::Identifier(pair.as_str()),
+        Rule::True => ExpressionKind::BooleanLiteral(true),
+        Rule::False => ExpressionKind::BooleanLiteral(false),
+        Rule::integer => ExpressionKind::IntegerLiteral(lower_integer(pair)?),
+        Rule::float => ExpressionKind::FloatLiteral(lower_float(pair)?),
+        Rule::char => ExpressionKind::CharLiteral(lower_char(pair)?),
+        Rule::string => ExpressionKind::StringLiteral(lower_string(pair)?),
+        Rule::list => ExpressionKind::ListLiteral(
+            pair.into_inner()
+                .map(lower_expression)
+                .collect::<Result<_>>()?,
+        ),
+        Rule::struct_literal => ExpressionKind::StructLiteral(lower_struct_literal(pair)?),
+        Rule::variant => ExpressionKind::VariantLiteral(Box::new(lower_variant(pair)?)),
+        Rule::assignment => ExpressionKind::Assignment(Box::new(lower_assignment(pair)?)),
+        Rule::reassignment => ExpressionKind::Reassignment(Box::new(lower_reassignment(pair)?)),
+        Rule::function_call => ExpressionKind::FunctionCall(lower_function_call(pair)?),
+        Rule::if_expr => ExpressionKind::If(Box::new(lower_if(pair)?)),
+        Rule::match_expr => ExpressionKind::Match(Box::new(lower_match(pair)?)),
+        Rule::lambda => ExpressionKind::Lambda(Box::new(lower_lambda(pair)?)),
+        Rule::block => ExpressionKind::Block(Box::new(lower_block(pair)?)),
+        rule => unreachable!("{:?} is not an expression", rule),
+    };
+
+    Ok(Expression::new(kind, span))
+}
+
+fn lower_integer(pair: Pair<'_, Rule>) -> Result<i64> {
+    pair.as_str()
+        .parse()
+        .map_err(|_| Error::custom(pair.as_span(), "integer literal is too large"))
+}
+
+fn lower_float(pair: Pair<'_, Rule>) -> Result<f64> {
+    pair.as_str()
+        .parse()
+        .map_err(|_| Error::custom(pair.as_span(), "invalid float literal"))
+}
+
+fn lower_char(pair: Pair<'_, Rule>) -> Result<char> {
+    let text = pair.as_str();
+
+    match pair.into_inner().next() {
+        Some(escape) => lower_escape(escape),
+        None => Ok(text[1..].chars().next().expect("empty char literal")),
+    }
+}
+
+fn lower_string(pair: Pair<'_, Rule>) -> Result<Cow<'_, str>> {
+    let pairs: Vec<_> = pair.into_inner().collect();
+
+    // Strings without escapes are borrowed from the source
+    match pairs.as_slice() {
+        [] => Ok(Cow::Borrowed("")),
+        [raw] if raw.as_rule() == Rule::raw_string => Ok(Cow::Borrowed(raw.as_str())),
+        _ => {
+            let mut string = String::new();
+            for pair in pairs {
+                match pair.as_rule() {
+                    Rule::raw_string => string.push_str(pair.as_str()),
+                    _ => string.push(lower_escape(pair)?),
+                }
+            }
+            Ok(Cow::Owned(string))
+        }
+    }
+}
+
+fn lower_escape(pair: Pair<'_, Rule>) -> Result<char> {
+    let span = pair.as_span();
+    let escape = only_child(pair);
+
+    match escape.as_rule() {
+        Rule::predefined => Ok(match escape.as_str() {
+            "n" => '\n',
+            "r" => '\r',
+            "t" => '\t',
+            "0" => '\0',
+            other => other.chars().next().expect("empty escape"),
+        }),
+        Rule::byte => match u8::from_str_radix(&escape.as_str()[1..], 16) {
+            Ok(byte) if byte.is_ascii() => Ok(byte as char),
+            _ => Err(Error::custom(
+                span,
+                "byte escapes must be in the range \\x00-\\x7F",
+            )),
+        },
+        Rule::unicode => {
+            let hex = only_child(escape).as_str();
+            u32::from_str_radix(hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| Error::custom(span, "invalid unicode escape"))
+        }
+        rule => unreachable!("{:?} is not an escape", rule),
+    }
+}
+
+/// Lowers a single expression between parentheses into itself, and anything else into a tuple
+fn lower_parenthesized(pair: Pair<'_, Rule>) -> Result<Expression<'_>> {
+    let span = span_of(&pair);
+    let mut items = Vec::new();
+    let mut trailing_comma = false;
+
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::trailing_comma => trailing_comma = true,
+            _ => items.push(lower_expression(pair)?),
+        }
+    }
+
+    if items.len() == 1 && !trailing_comma {
+        return Ok(items.remove(0));
+    }
+
+    Ok(Expression::new(ExpressionKind::TupleLiteral(items), span))
+}
+
+fn lower_struct_literal(pair: Pair<'_, Rule>) -> Result<StructLiteral<'_>> {
+    let mut pairs = pair.into_inner();
+    let name = pairs.next().expect("struct literal without name").as_str();
+    let fields = pairs
+        .map(|pair| {
+            let mut pairs = pair.into_inner();
+            let name = pairs.next().expect("field without name").as_str();
+            let expression = lower_expression(pairs.next().expect("field without value"))?;
+            Ok(FieldValue { name, expression })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(StructLiteral { name, fields })
+}
+
+fn lower_variant(pair: Pair<'_, Rule>) -> Result<VariantLiteral<'_>> {
+    let mut pairs = pair.into_inner();
+    let ty = pairs.next().expect("variant without type").as_str();
+    let name = pairs.next().expect("variant without name").as_str();
+    let params = pairs.map(lower_expression).collect::<Result<_>>()?;
+
+    Ok(VariantLiteral { ty, name, params })
+}
+
+fn lower_assignment(pair: Pair<'_, Rule>) -> Result<Assignment<'_>> {
+    let mut pattern = None;
+    let mut type_annotation = None;
+    let mut expression = None;
+
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::let_ => {}
+            Rule::identifier | Rule::tuple_pattern => pattern = Some(lower_pattern(pair)?),
+            Rule::type_annotation => type_annotation = Some(lower_type_annotation(pair)),
+            _ => expression = Some(lower_expression(pair)?),
+        }
+    }
+
+    Ok(Assignment {
+        pattern: pattern.expect("assignment without pattern"),
+        type_annotation,
+        expression: expression.expect("assignment without expression"),
+    })
+}
+
+fn lower_pattern(pair: Pair<'_, Rule>) -> Result<Pattern<'_>> {
+    let pattern = match pair.as_rule() {
+        Rule::identifier => Pattern::Identifier(pair.as_str()),
+        Rule::wildcard => Pattern::Wildcard,
+        Rule::tuple_pattern | Rule::tuple_match_pattern => Pattern::Tuple(
+            pair.into_inner()
+                .map(lower_pattern)
+                .collect::<Result<_>>()?,
+        ),
+        Rule::literal_pattern => {
+            let span = span_of(&pair);
+            let mut pairs = pair.into_inner();
+            let first = pairs.next().expect("literal pattern without literal");
+            match first.as_rule() {
+                Rule::unary_minus => {
+                    let literal = lower_expression(pairs.next().expect("minus without literal"))?;
+                    Pattern::Literal(Expression::new(
+                        ExpressionKind::Minus(Box::new(literal)),
+                        span,
+                    ))
+                }
+                _ => Pattern::Literal(lower_expression(first)?),
+            }
+        }
+        Rule::variant_pattern => {
+            let mut pairs = pair.into_inner();
+            let path = pairs.next().expect("variant pattern without variant");
+            let (ty, variant) = match path.as_rule() {
+                Rule::variant_path => {
+                    let mut names = path.into_inner().map(|pair| pair.as_str());
+                    let ty = names.next().expect("variant without type");
+                    (Some(ty), names.next().expect("variant without name"))
+                }
+                _ => (None, path.as_str()),
+            };
+            let payload = pairs.next().map(lower_pattern).transpose()?;
+
+            Pattern::Variant(VariantPattern {
+                ty,
+                variant,
+                payload: payload.map(Box::new),
+            })
+        }
+        Rule::struct_pattern => {
+            let mut pairs = pair.into_inner();
+            let name = pairs.next().expect("struct pattern without name").as_str();
+            let mut fields = Vec::new();
+            let mut rest = false;
+            for pair in pairs {
+                match pair.as_rule() {
+                    Rule::rest => rest = true,
+                    _ => {
+                        let mut pairs = pair.into_inner();
+                        let name = pairs.next().expect("field without name").as_str();
+                        // `Point { x }` binds the field to a variable with its name
+                        let pattern = match pairs.next() {
+                            Some(pattern) => lower_pattern(pattern)?,
+                            None => Pattern::Identifier(name),
+                        };
+                        fields.push(FieldPattern { name, pattern });
+                    }
+                }
+            }
+
+            Pattern::Struct(StructPattern { name, fields, rest })
+        }
+        rule => unreachable!("{:?} is not a pattern", rule),
+    };
+
+    Ok(pattern)
+}
+
+fn lower_lambda(pair: Pair<'_, Rule>) -> Result<Lambda<'_>> {
+    let mut params = Vec::new();
+    let mut body = None;
+
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::fn_ => {}
+            Rule::identifier => params.push(pair.as_str()),
+            _ => body = Some(lower_expression(pair)?),
+        }
+    }
+
+    Ok(Lambda {
+        params,
+        body: body.expect("lambda without body"),
+    })
+}
+
+fn lower_match(pair: Pair<'_, Rule>) -> Result<Match<'_>> {
+    let mut pairs = pair
+        .into_inner()
+        .skip_while(|pair| pair.as_rule() == Rule::match_);
+    let scrutinee = lower_expression(pairs.next().expect("match without value"))?;
+
+    let arms = pairs
+        .map(|arm| {
+            let span = span_of(&arm);
+            let mut guard = None;
+            let mut pairs = arm.into_inner();
+            let pattern = lower_pattern(pairs.next().expect("arm without pattern"))?;
+            let mut body = pairs.next().expect("arm without body");
+            if body.as_rule() == Rule::guard {
+                let condition = body.into_inner().last().expect("guard without condition");
+                guard = Some(lower_expression(condition)?);
+                body = pairs.next().expect("arm without body");
+            }
+
+            Ok(MatchArm {
+                pattern,
+                guard,
+                body: lower_expression(body)?,
+                span,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Match { scrutinee, arms })
+}
+
+fn lower_assignee(pair: Pair<'_, Rule>) -> Assignee<'_> {
+    match pair.as_rule() {
+        Rule::identifier => Assignee::Variable(pair.as_str()),
+        Rule::tuple_assignee => Assignee::Tuple(pair.into_inner().map(lower_assignee).collect()),
+        Rule::field_assignee => {
+            let mut names = pair.into_inner().map(|pair| pair.as_str());
+            let variable = Assignee::Variable(names.next().expect("field without variable"));
+            names.fold(variable, |base, field| Assignee::Field {
+                base: Box::new(base),
+                field,
+            })
+        }
+        rule => unreachable!("{:?} is not an assignee", rule),
+    }
+}
+
+fn lower_reassignment(pair: Pair<'_, Rule>) -> Result<Reassignment<'_>> {
+    let mut pairs = pair.into_inner();
+    let target = lower_assignee(pairs.next().expect("reassignment without assignee"));
+    let op = match pairs
+        .next()
+        .expect("reassignment without operator")
+        .as_rule()
+    {
+        Rule::assign => None,
+        Rule::add_assign => Some(ArithmeticOperator::Add),
+        Rule::subtract_assign => Some(ArithmeticOperator::Subtract),
+        Rule::multiply_assign => Some(ArithmeticOperator::Multiply),
+        Rule::divide_assign => Some(ArithmeticOperator::Divide),
+        Rule::modulo_assign => Some(ArithmeticOperator::Modulo),
+        Rule::power_assign => Some(ArithmeticOperator::Power),
+        rule => unreachable!("{:?} is not an assignment operator", rule),
+    };
+    let expression = lower_expression(pairs.next().expect("reassignment without expression"))?;
+
+    Ok(Reassignment {
+        target,
+        op,
+        expression,
+    })
+}
+
+fn lower_function_call(pair: Pair<'_, Rule>) -> Result<FunctionCall<'_>> {
+    let mut pairs = pair.into_inner();
+    let name = pairs.next().expect("function call without name").as_str();
+    let params = pairs.map(lower_expression).collect::<Result<_>>()?;
+
+    Ok(FunctionCall { name, params })
+}
+
+fn lower_method_call<'a>(receiver: Expression<'a>, pair: Pair<'a, Rule>) -> Result<MethodCall<'a>> {
+    let mut pairs = pair.into_inner();
+    let name = pairs.next().expect("method call without name").as_str();
+    let params = pairs.map(lower_expression).collect::<Result<_>>()?;
+
+    Ok(MethodCall {
+        receiver,
+        name,
+        params,
+    })
+}
+
+/// How tightly each operator binds its operands, from the loosest to the tightest:
+///
+/// | Operators                      | Associativity |
+/// |--------------------------------|---------------|
+/// | `or` `nor`                     | left          |
+/// | `xor`                          | left          |
+/// | `and` `nand`                   | left          |
+/// | `not`                          | prefix        |
+/// | `==` `!=` `<` `<=` `>` `>=`    | left          |
+/// | `|`                            | left          |
+/// | `~` (xor)                      | left          |
+/// | `&`                            | left          |
+/// | `<<` `>>`                      | left          |
+/// | `+` `-`                        | left          |
+/// | `*` `/` `%`                    | left          |
+/// | `-` (negation) `~` (not)       | prefix        |
+/// | `^`                            | right         |
+/// | `[]` (indexing) `.` (access)   | postfix       |
+///
+/// So `not a == b and c` is `(not (a == b)) and c`, `a or b xor c` is `a or (b xor c)`,
+/// `a & b == c` is `(a & b) == c`, `a ~ b & c` is `a ~ (b & c)` and `-2 ^ 2` is `-(2 ^ 2)`.
+///
+/// Infix operators have a left and a right binding power: the right one being
+/// greater makes them left-associative, and vice versa.
+fn infix_binding_power(rule: Rule) -> (u8, u8) {
+    match rule {
+        Rule::or | Rule::nor => (1, 2),
+        Rule::xor => (3, 4),
+        Rule::and | Rule::nand => (5, 6),
+        Rule::equals
+        | Rule::not_equals
+        | Rule::less
+        | Rule::less_equals
+        | Rule::greater
+        | Rule::greater_equals => (9, 10),
+        Rule::bit_or => (11, 12),
+        Rule::bit_xor => (13, 14),
+        Rule::bit_and => (15, 16),
+        Rule::shift_left | Rule::shift_right => (17, 18),
+        Rule::add | Rule::subtract => (19, 20),
+        Rule::multiply | Rule::divide | Rule::modulo => (21, 22),
+        Rule::power => (26, 25),
+        rule => unreachable!("{:?} is not an infix operator", rule),
+    }
+}
+
+fn prefix_binding_power(rule: Rule) -> Option<u8> {
+    match rule {
+        Rule::not => Some(7),
+        Rule::unary_minus | Rule::bit_not => Some(23),
+        _ => None,
+    }
+}
+
+fn postfix_binding_power(rule: Rule) -> Option<u8> {
+    match rule {
+        Rule::index | Rule::method_call | Rule::field => Some(27),
+        _ => None,
+    }
+}
+
+/// Lowers an [`Rule::operation`], a flat sequence of terms and operators, into a tree
+/// that respects the precedence of each operator
+fn lower_operation(pair: Pair<'_, Rule>) -> Result<Expression<'_>> {
+    lower_operand(&mut pair.into_inner().peekable(), 0)
+}
+
+/// Lowers the longest operation starting at `pairs` whose operators bind
+/// at least as tightly as `min_power`
+fn lower_operand<'a>(
+    pairs: &mut Peekable<Pairs<'a, Rule>>,
+    min_power: u8,
+) -> Result<Expression<'a>> {
+    let first = pairs.next().expect("operation without operand");
+
+    let mut left = match prefix_binding_power(first.as_rule()) {
+        Some(power) => {
+            let operand = lower_operand(pairs, power)?;
+            let span = span_of(&first).to(operand.span);
+            let kind = match first.as_rule() {
+                Rule::not => ExpressionKind::Not(Box::new(operand)),
+                Rule::bit_not => ExpressionKind::BitNot(Box::new(operand)),
+                _ => ExpressionKind::Minus(Box::new(operand)),
+            };
+            Expression::new(kind, span)
+        }
+        None => lower_expression(first)?,
+    };
+
+    while let Some(op) = pairs.peek() {
+        if let Some(power) = postfix_binding_power(op.as_rule()) {
+            if power < min_power {
+                break;
+            }
+
+            let op = pairs.next().expect("peeked operator");
+            let span = left.span.to(span_of(&op));
+            let kind = match op.as_rule() {
+                Rule::index => ExpressionKind::Index(Box::new(Index {
+                    indexed: left,
+                    index: lower_expression(only_child(op))?,
+                })),
+                Rule::method_call => {
+                    ExpressionKind::MethodCall(Box::new(lower_method_call(left, op)?))
+                }
+                _ => ExpressionKind::FieldAccess(Box::new(FieldAccess {
+                    base: left,
+                    field: only_child(op).as_str(),
+                })),
+            };
+            left = Expression::new(kind, span);
+            continue;
+        }
+
+        let (left_power, right_power) = infix_binding_power(op.as_rule());
+        if left_power < min_power {
+            break;
+        }
+
+        let op = pairs.next().expect("peeked operator").as_rule();
+        let right = lower_operand(pairs, right_power)?;
+        let span = left.span.to(right.span);
+        left = Expression::new(lower_binary_operation(left, op, right), span);
+    }
+
+    Ok(left)
+}
+
+fn lower_binary_operation<'a>(
+    left: Expression<'a>,
+    op: Rule,
+    right: Expression<'a>,
+) -> ExpressionKind<'a> {
+    match op {
+        Rule::or | Rule::xor | Rule::and | Rule::nand | Rule::nor => {
+            let op = match op {
+                Rule::or => BooleanOperator::Or,
+                Rule::xor => BooleanOperator::Xor,
+                Rule::nand => BooleanOperator::Nand,
+                Rule::nor => BooleanOperator::Nor,
+                _ => BooleanOperator::And,
+            };
+            ExpressionKind::BoolOperation(Box::new(BooleanOperation { left, op, right }))
+        }
+        Rule::bit_and | Rule::bit_or | Rule::bit_xor | Rule::shift_left | Rule::shift_right => {
+            let op = match op {
+                Rule::bit_and => BitwiseOperator::BitAnd,
+                Rule::bit_or => BitwiseOperator::BitOr,
+                Rule::bit_xor => BitwiseOperator::BitXor,
+                Rule::shift_left => BitwiseOperator::ShiftLeft,
+                _ => BitwiseOperator::ShiftRight,
+            };
+            ExpressionKind::BitOperation(Box::new(BitwiseOperation { left, op, right }))
+        }
+        Rule::equals
+        | Rule::not_equals
+        | Rule::less
+        | Rule::less_equals
+        | Rule::greater
+        | Rule::greater_equals => {
+            let op = match op {
+                Rule::equals => ComparisonOperator::Equals,
+                Rule::not_equals => ComparisonOperator::NotEquals,
+                Rule::less => ComparisonOperator::Less,
+                Rule::less_equals => ComparisonOperator::LessEquals,
+                Rule::greater => ComparisonOperator::Greater,
+                _ => ComparisonOperator::GreaterEquals,
+            };
+            ExpressionKind::CompOperation(Box::new(ComparisonOperation { left, op, right }))
+        }
+        _ => {
+            let op = match op {
+                Rule::add => ArithmeticOperator::Add,
+                Rule::subtract => ArithmeticOperator::Subtract,
+                Rule::multiply => ArithmeticOperator::Multiply,
+                Rule::divide => ArithmeticOperator::Divide,
+                Rule::power => ArithmeticOperator::Power,
+                Rule::modulo => ArithmeticOperator::Modulo,
+                rule => unreachable!("{:?} is not an arithmetic operator", rule),
+            };
+            ExpressionKind::ArithOperation(Box::new(ArithmeticOperation { left, op, right }))
+        }
+    }
+}
+
+fn lower_if(pair: Pair<'_, Rule>) -> Result<If<'_>> {
+    let mut pairs = pair.into_inner();
+    let condition = lower_expression(pairs.next().expect("if without condition"))?;
+    let then = lower_expression(pairs.next().expect("if without body"))?;
+
+    let mut elifs = Vec::new();
+    let mut otherwise = None;
+
+    for pair in pairs {
+        match pair.as_rule() {
+            Rule::elif => {
+                let mut pairs = pair.into_inner();
+                let condition = lower_expression(pairs.next().expect("elif without condition"))?;
+                let then = lower_expression(pairs.next().expect("elif without body"))?;
+                elifs.push(Elif { condition, then });
+            }
+            _ => otherwise = Some(lower_expression(pair)?),
+        }
+    }
+
+    Ok(If {
+        condition,
+        then,
+        elifs,
+        otherwise,
+    })
+}
+
+fn lower_block(pair: Pair<'_, Rule>) -> Result<Block<'_>> {
+    let mut statements = Vec::new();
+    let mut tail = None;
+
+    for pair in pair.into_inner() {
+        // Every expression is the tail until another one follows it or a semicolon discards its value
+        statements.extend(tail.take());
+        if pair.as_rule() != Rule::semicolon {
+            tail = Some(lower_expression(pair)?);
+        }
+    }
+
+    Ok(Block { statements, tail })
+}
+
+fn span_of(pair: &Pair<'_, Rule>) -> AstSpan {
+    let span = pair.as_span();
+    AstSpan::new(span.start(), span.end())
+}
+
+/// Returns the only inner pair of `pair`
+fn only_child(pair: Pair<'_, Rule>) -> Pair<'_, Rule> {
+    pair.into_inner().next().expect("expected an inner rule")
+}
+
+#[cfg(test)]
+mod lowering_tests {
+    use runner::ast::{
+        ArithmeticOperator, Assignee, BooleanOperator, Expression, ExpressionKind, HighLevelItem,
+        Pattern, Span,
+    };
+
+    use super::parse;
+
+    fn parse_expression(source: &str) -> ExpressionKind<'_> {
+        parse_spanned(source).kind
+    }
+
+    fn parse_spanned(source: &str) -> Expression<'_> {
+        let mut program = parse(source).expect("failed to parse");
+        assert_eq!(program.items.len(), 1);
+
+        match program.items.remove(0) {
+            HighLevelItem::Expr(expression) => expression,
+            item => panic!("expected an expression, found {:?}", item),
+        }
+    }
+
+    #[test]
+    fn literals() {
+        assert!(matches!(
+            parse_expression("123"),
+            ExpressionKind::IntegerLiteral(123)
+        ));
+        assert!(matches!(
+            parse_expression("true"),
+            ExpressionKind::BooleanLiteral(true)
+        ));
+        assert!(matches!(
+            parse_expression("'c'"),
+            ExpressionKind::CharLiteral('c')
+        ));
+        assert!(matches!(
+            parse_expression("'\\n'"),
+            ExpressionKind::CharLiteral('\n')
+        ));
+        assert!(
+            matches!(parse_expression("1.5"), ExpressionKind::FloatLiteral(float) if float == 1.5)
+        );
+        assert!(
+            matches!(parse_expression("\"hey\""), ExpressionKind::StringLiteral(string) if string == "hey")
+        );
+        assert!(
+            matches!(parse_expression("\"a\\tb\\u{e9}\""), ExpressionKind::StringLiteral(string) if string == "a\tbé")
+        );
+        assert!(
+            matches!(parse_expression("\"  a b \""), ExpressionKind::StringLiteral(string) if string == "  a b ")
+        );
+        assert!(matches!(
+            parse_expression("' '"),
+            ExpressionKind::CharLiteral(' ')
+        ));
+
+        assert!(parse("99999999999999999999").is_err());
+        assert!(parse("\"\\xFF\"").is_err());
+    }
+
+    #[test]
+    fn operations() {
+        let expression = parse_expression("2 + 3 - x");
+        let ExpressionKind::ArithOperation(outer) = expression else {
+            panic!("expected an arithmetic operation");
+        };
+        assert!(matches!(outer.op, ArithmeticOperator::Subtract));
+        assert!(matches!(outer.right.kind, ExpressionKind::Identifier("x")));
+
+        let expression = parse_expression("not (true or false)");
+        let ExpressionKind::Not(inner) = expression else {
+            panic!("expected a boolean NOT");
+        };
+        assert!(
+            matches!(inner.kind, ExpressionKind::BoolOperation(ref op) if matches!(op.op, BooleanOperator::Or))
+        );
+    }
+
+    /// Shows the structure of an operation, with every binary operation in parentheses
+    fn show(expression: &Expression) -> String {
+        match &expression.kind {
+            ExpressionKind::Identifier(name) => name.to_string(),
+            ExpressionKind::IntegerLiteral(integer) => integer.to_string(),
+            ExpressionKind::BooleanLiteral(boolean) => boolean.to_string(),
+            ExpressionKind::Not(operand) => format!("(not {})", show(operand)),
+            ExpressionKind::Minus(operand) => format!("(-{})", show(operand)),
+            ExpressionKind::BitNot(operand) => format!("(~{})", show(operand)),
+            ExpressionKind::ArithOperation(op) => {
+                format!("({} {:?} {})", show(&op.left), op.op, show(&op.right))
+            }
+            ExpressionKind::CompOperation(op) => {
+                format!("({} {:?} {})", show(&op.left), op.op, show(&op.right))
+            }
+            ExpressionKind::BoolOperation(op) => {
+                format!("({} {:?} {})", show(&op.left), op.op, show(&op.right))
+            }
+            ExpressionKind::BitOperation(op) => {
+                format!("({} {:?} {})", show(&op.left), op.op, show(&op.right))
+            }
+            ExpressionKind::Index(index) => {
+                format!("{}[{}]", show(&index.indexed), show(&index.index))
+            }
+            ExpressionKind::FieldAccess(access) => {
+                format!("{}.{}", show(&access.base), access.field)
+            }
+            ExpressionKind::MethodCall(call) => {
+                let params: Vec<_> = call.params.iter().map(show).collect();
+                format!(
+                    "{}.{}({})",
+                    show(&call.receiver),
+                    call.name,
+                    params.join(", ")
+                )
+            }
+            kind => panic!("unexpected {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn precedence() {
+        let cases = [
+            ("10 + 10 * 10", "(10 Add (10 Multiply 10))"),
+            ("(10 + 10) * 10", "((10 Add 10) Multiply 10)"),
+            ("1 - 2 - 3", "((1 Subtract 2) Subtract 3)"),
+            ("2 ^ 3 ^ 2", "(2 Power (3 Power 2))"),
+            ("-2 ^ 2", "(-(2 Power 2))"),
+            ("5 - -2 * 3", "(5 Subtract ((-2) Multiply 3))"),
+            ("a % 2 == 0", "((a Modulo 2) Equals 0)"),
+            ("a != b == c", "((a NotEquals b) Equals c)"),
+            ("not a == b and c", "((not (a Equals b)) And c)"),
+            ("not not a or b", "((not (not a)) Or b)"),
+            ("a or b and c", "(a Or (b And c))"),
+            ("a and b xor c", "((a And b) Xor c)"),
+            ("a xor b or c", "((a Xor b) Or c)"),
+            ("a or b xor c", "(a Or (b Xor c))"),
+            ("a xor b and c", "(a Xor (b And c))"),
+            ("a nand b xor c nor d", "(((a Nand b) Xor c) Nor d)"),
+            ("a nor b or c", "((a Nor b) Or c)"),
+            ("not a nand b", "((not a) Nand b)"),
+            ("a & b == c", "((a BitAnd b) Equals c)"),
+            ("a | b & c", "(a BitOr (b BitAnd c))"),
+            ("a & b << 1 + 1", "(a BitAnd (b ShiftLeft (1 Add 1)))"),
+            ("a << 1 >> 2 < b", "(((a ShiftLeft 1) ShiftRight 2) Less b)"),
+            ("a | b and c", "((a BitOr b) And c)"),
+            ("~a & -b", "((~a) BitAnd (-b))"),
+            ("a | b ~ c & d", "(a BitOr (b BitXor (c BitAnd d)))"),
+            ("a ~ ~b == c", "((a BitXor (~b)) Equals c)"),
+            ("~a ^ 2", "(~(a Power 2))"),
+            ("-a[0] ^ 2", "(-(a[0] Power 2))"),
+            ("a[b[0]][1 + 1]", "a[b[0]][(1 Add 1)]"),
+            ("-t.get(0)[1]", "(-t.get(0)[1])"),
+            ("a.get(1 + 1) == b", "(a.get((1 Add 1)) Equals b)"),
+            (
+                "not joao.friends[0].is_vaccinated",
+                "(not joao.friends[0].is_vaccinated)",
+            ),
+            ("-a.b ^ c.d", "(-(a.b Power c.d))"),
+        ];
+
+        for (source, expected) in cases {
+            assert_eq!(show(&parse_spanned(source)), expected, "{}", source);
+        }
+    }
+
+    #[test]
+    fn lists() {
+        let ExpressionKind::ListLiteral(items) = parse_expression("[5, 10, 15]") else {
+            panic!("expected a list");
+        };
+        assert_eq!(items.len(), 3);
+        assert!(matches!(items[2].kind, ExpressionKind::IntegerLiteral(15)));
+
+        let source = "lista[-1]";
+        let ExpressionKind::Index(index) = parse_expression(source) else {
+            panic!("expected an index");
+        };
+        assert!(matches!(index.index.kind, ExpressionKind::Minus(_)));
+        assert_eq!(parse_spanned(source).span, Span::new(0, source.len()));
+    }
+
+    #[test]
+    fn structs() {
+        let program =
+            parse("struct Wallet { coins: list<int>, owner: User, }").expect("failed to parse");
+        let HighLevelItem::Struct(ref wallet) = program.items[0] else {
+            panic!("expected a struct");
+        };
+        assert!(!wallet.public);
+        assert_eq!(wallet.fields.len(), 2);
+        assert!(!wallet.fields[0].public);
+        assert_eq!(wallet.fields[0].type_, "list<int>");
+        assert_eq!(wallet.fields[1].type_, "User");
+
+        let ExpressionKind::StructLiteral(literal) = parse_expression("Point { x: 1, y: 2 + 3 }")
+        else {
+            panic!("expected a struct literal");
+        };
+        assert_eq!(literal.name, "Point");
+        assert_eq!(literal.fields.len(), 2);
+        assert_eq!(literal.fields[1].name, "y");
+        assert!(matches!(
+            literal.fields[1].expression.kind,
+            ExpressionKind::ArithOperation(_)
+        ));
+
+        let ExpressionKind::Reassignment(reassignment) = parse_expression("a.b.c = 1") else {
+            panic!("expected a reassignment");
+        };
+        assert_eq!(reassignment.target.to_string(), "a.b.c");
+        let Assignee::Field { base, field } = reassignment.target else {
+            panic!("expected a field");
+        };
+        assert_eq!(field, "c");
+        assert!(matches!(*base, Assignee::Field { field: "b", .. }));
+    }
+
+    #[test]
+    fn tuples() {
+        let ExpressionKind::TupleLiteral(items) = parse_expression("(10, 20)") else {
+            panic!("expected a tuple");
+        };
+        assert_eq!(items.len(), 2);
+
+        // A single item needs a trailing comma, otherwise it's only parenthesized
+        let ExpressionKind::TupleLiteral(items) = parse_expression("(\"one\",)") else {
+            panic!("expected a tuple");
+        };
+        assert_eq!(items.len(), 1);
+        assert!(matches!(
+            parse_expression("(1)"),
+            ExpressionKind::IntegerLiteral(1)
+        ));
+
+        let ExpressionKind::Assignment(assignment) = parse_expression("let (a, (b, c)) = t") else {
+            panic!("expected an assignment");
+        };
+        let Pattern::Tuple(patterns) = assignment.pattern else {
+            panic!("expected a tuple pattern");
+        };
+        assert!(matches!(patterns[0], Pattern::Identifier("a")));
+        assert!(matches!(patterns[1], Pattern::Tuple(ref inner) if inner.len() == 2));
+
+        let ExpressionKind::Reassignment(reassignment) = parse_expression("(a, b) = (b, a)") else {
+            panic!("expected a reassignment");
+        };
+        assert!(matches!(reassignment.target, Assignee::Tuple(ref targets) if targets.len() == 2));
+        assert!(matches!(
+            reassignment.expression.kind,
+            ExpressionKind::TupleLiteral(_)
+        ));
+
+        assert!(matches!(
+            parse_expression("(a, b) == (b, a)"),
+            ExpressionKind::CompOperation(_)
+        ));
+    }
+
+    #[test]
+    fn assignments_and_calls() {
+        let ExpressionKind::Assignment(assignment) = parse_expression("let six: int = 6;") else {
+            panic!("expected an assignment");
+        };
+        assert!(matches!(assignment.pattern, Pattern::Identifier("six")));
+        assert_eq!(assignment.type_annotation, Some("int"));
+        assert!(matches!(
+            assignment.expression.kind,
+            ExpressionKind::IntegerLiteral(6)
+        ));
+
+        let ExpressionKind::FunctionCall(call) = parse_expression("println(\"haha\", 'c', 2)")
+        else {
+            panic!("expected a function call");
+        };
+        assert_eq!(call.name, "println");
+        assert_eq!(call.params.len(), 3);
+    }
+
+    #[test]
+    fn reassignments() {
+        let ExpressionKind::Reassignment(reassignment) = parse_expression("a = a + 10") else {
+            panic!("expected a reassignment");
+        };
+        assert!(matches!(reassignment.target, Assignee::Variable("a")));
+        assert!(reassignment.op.is_none());
+
+        let ExpressionKind::Reassignment(reassignment) = parse_expression("i %= 2") else {
+            panic!("expected a reassignment");
+        };
+        assert!(matches!(reassignment.op, Some(ArithmeticOperator::Modulo)));
+
+        assert!(matches!(
+            parse_expression("a == 2"),
+            ExpressionKind::CompOperation(_)
+        ));
+    }
+
+    #[test]
+    fn if_elif_else() {
+        let ExpressionKind::If(if_) =
+            parse_expression("if a { 1 } elif b { 2 } elif c { 3 } else { 4 }")
+        else {
+            panic!("expected an if expression");
+        };
+        assert!(matches!(
+            if_.condition.kind,
+            ExpressionKind::Identifier("a")
+        ));
+        assert_eq!(if_.elifs.len(), 2);
+        let Some(ExpressionKind::Block(otherwise)) = if_.otherwise.map(|otherwise| otherwise.kind)
+        else {
+            panic!("expected an else block");
+        };
+        assert!(matches!(
+            otherwise.tail.map(|tail| tail.kind),
+            Some(ExpressionKind::IntegerLiteral(4))
+        ));
+
+        let ExpressionKind::If(if_) = parse_expression("if a { 1 }") else {
+            panic!("expected an if expression");
+        };
+        assert!(if_.elifs.is_empty());
+        assert!(if_.otherwise.is_none());
+    }
+
+    #[test]
+    fn blocks() {
+        let ExpressionKind::Block(block) = parse_expression("{ let x = 2; println(x); x * x }")
+        else {
+            panic!("expected a block");
+        };
+        assert_eq!(block.statements.len(), 2);
+        assert!(matches!(
+            block.tail.map(|tail| tail.kind),
+            Some(ExpressionKind::ArithOperation(_))
+        ));
+
+        // A trailing semicolon discards the value of the last statement
+        let ExpressionKind::Block(block) = parse_expression("{ println(1); println(2); }") else {
+            panic!("expected a block");
+        };
+        assert_eq!(block.statements.len(), 2);
+        assert!(block.tail.is_none());
+
+        let ExpressionKind::Block(block) = parse_expression("{}") else {
+            panic!("expected a block");
+        };
+        assert!(block.statements.is_empty());
+        assert!(block.tail.is_none());
+    }
+
+    #[test]
+    fn items() {
+        let program = parse(
+            r#"
+            pub struct UserData { pub name: string }
+            pub enum User { Admin(UserData), Team(list<UserData>), Guest }
+            pub fn double x: int -> int { x }
+            fn zero { 0 }
+            "#,
+        )
+        .expect("failed to parse");
+
+        let HighLevelItem::Struct(ref user_data) = program.items[0] else {
+            panic!("expected a struct");
+        };
+        assert!(user_data.public);
+        assert_eq!(user_data.name, "UserData");
+        assert_eq!(user_data.fields.len(), 1);
+        assert!(user_data.fields[0].public);
+        assert_eq!(user_data.fields[0].name, "name");
+        assert_eq!(user_data.fields[0].type_, "string");
+
+        let HighLevelItem::Enum(ref user) = program.items[1] else {
+            panic!("expected an enum");
+        };
+        assert!(user.public);
+        assert_eq!(user.name, "User");
+        assert_eq!(user.variants.len(), 3);
+        assert_eq!(user.variants[0].name, "Admin");
+        assert_eq!(user.variants[0].payload, Some("UserData"));
+        assert_eq!(user.variants[1].payload, Some("list<UserData>"));
+        assert_eq!(user.variants[2].name, "Guest");
+        assert_eq!(user.variants[2].payload, None);
+
+        let HighLevelItem::Fn(ref double) = program.items[2] else {
+            panic!("expected a function");
+        };
+        assert!(double.public);
+        assert_eq!(double.name, "double");
+        assert_eq!(double.params.len(), 1);
+        assert_eq!(double.params[0].type_, "int");
+        assert_eq!(double.output, Some("int"));
+
+        let HighLevelItem::Fn(ref zero) = program.items[3] else {
+            panic!("expected a function");
+        };
+        assert!(!zero.public);
+        assert_eq!(zero.output, None);
+    }
+
+    #[test]
+    fn variants() {
+        let ExpressionKind::VariantLiteral(admin) = parse_expression("User::Admin(data)") else {
+            panic!("expected a variant");
+        };
+        assert_eq!(admin.ty, "User");
+        assert_eq!(admin.name, "Admin");
+        assert!(matches!(
+            admin.params[..],
+            [Expression {
+                kind: ExpressionKind::Identifier("data"),
+                ..
+            }]
+        ));
+
+        let ExpressionKind::VariantLiteral(ready) = parse_expression("Status::Ready") else {
+            panic!("expected a variant");
+        };
+        assert_eq!(ready.ty, "Status");
+        assert_eq!(ready.name, "Ready");
+        assert!(ready.params.is_empty());
+
+        // Functions of a type are named the same way
+        let ExpressionKind::VariantLiteral(new) = parse_expression("Point::new(1, 2)") else {
+            panic!("expected a variant");
+        };
+        assert_eq!(new.name, "new");
+        assert_eq!(new.params.len(), 2);
+
+        // Without the type, variants are resolved when running
+        assert!(matches!(
+            parse_expression("Admin(data)"),
+            ExpressionKind::FunctionCall(ref call) if call.name == "Admin"
+        ));
+    }
+
+    #[test]
+    fn lambdas() {
+        let ExpressionKind::Assignment(assignment) =
+            parse_expression("let sum = fn a, b { a + b }")
+        else {
+            panic!("expected an assignment");
+        };
+        let ExpressionKind::Lambda(ref lambda) = assignment.expression.kind else {
+            panic!("expected a lambda");
+        };
+        assert_eq!(lambda.params, ["a", "b"]);
+        assert!(matches!(lambda.body.kind, ExpressionKind::Block(_)));
+
+        let ExpressionKind::Lambda(lambda) = parse_expression("fn { 0 }") else {
+            panic!("expected a lambda");
+        };
+        assert!(lambda.params.is_empty());
+    }
+
+    #[test]
+    fn matches() {
+        let source = r#"
+            match value {
+                User::Admin(Point { x: 0, y, .. }) if y > 1 => { y }
+                (-1, 'a', _) => 2,
+                Guest => 3,
+            }
+        "#;
+        let ExpressionKind::Match(match_) = parse_expression(source) else {
+            panic!("expected a match");
+        };
+        assert!(matches!(
+            match_.scrutinee.kind,
+            ExpressionKind::Identifier("value")
+        ));
+        assert_eq!(match_.arms.len(), 3);
+
+        let admin = &match_.arms[0];
+        assert!(admin.guard.is_some());
+        assert!(matches!(admin.body.kind, ExpressionKind::Block(_)));
+        let Pattern::Variant(ref variant) = admin.pattern else {
+            panic!("expected a variant pattern");
+        };
+        assert_eq!(variant.ty, Some("User"));
+        assert_eq!(variant.variant, "Admin");
+        let Some(Pattern::Struct(ref point)) = variant.payload.as_deref() else {
+            panic!("expected a struct pattern");
+        };
+        assert_eq!(point.name, "Point");
+        assert!(point.rest);
+        assert!(matches!(
+            point.fields[0].pattern,
+            Pattern::Literal(Expression {
+                kind: ExpressionKind::IntegerLiteral(0),
+                ..
+            })
+        ));
+        // `y` is short for `y: y`
+        assert!(matches!(point.fields[1].pattern, Pattern::Identifier("y")));
+
+        let Pattern::Tuple(ref items) = match_.arms[1].pattern else {
+            panic!("expected a tuple pattern");
+        };
+        assert!(matches!(
+            items[0],
+            Pattern::Literal(Expression {
+                kind: ExpressionKind::Minus(_),
+                ..
+            })
+        ));
+        assert!(matches!(
+            items[1],
+            Pattern::Literal(Expression {
+                kind: ExpressionKind::CharLiteral('a'),
+                ..
+            })
+        ));
+        assert!(matches!(items[2], Pattern::Wildcard));
+
+        // Whether `Guest` is a variant is only known when running
+        assert!(match_.arms[2].guard.is_none());
+        assert!(matches!(
+            match_.arms[2].pattern,
+            Pattern::Identifier("Guest")
+        ));
+    }
+
+    #[test]
+    fn impls() {
+        let program = parse(
+            r#"
+            impl Person {
+                fn birthday self { self.age += 1; }
+                pub fn grow self, years: int -> Self { self }
+                fn zero -> int { 0 }
+            }
+            "#,
+        )
+        .expect("failed to parse");
+
+        let HighLevelItem::Impl(ref impl_) = program.items[0] else {
+            panic!("expected an impl block");
+        };
+        assert_eq!(impl_.ty, "Person");
+        assert_eq!(impl_.methods.len(), 3);
+        assert!(impl_.methods[0].receiver);
+        assert!(impl_.methods[0].params.is_empty());
+        assert!(impl_.methods[1].receiver);
+        assert!(impl_.methods[1].public);
+        assert_eq!(impl_.methods[1].params[0].name, "years");
+        assert_eq!(impl_.methods[1].output, Some("Self"));
+        assert!(!impl_.methods[2].receiver);
+    }
+
+    #[test]
+    fn spans() {
+        let source = "let x = 1 + 22";
+        let ExpressionKind::Assignment(assignment) = parse_expression(source) else {
+            panic!("expected an assignment");
+        };
+        assert_eq!(assignment.expression.span, Span::new(8, 14));
+
+        let ExpressionKind::ArithOperation(operation) = assignment.expression.kind else {
+            panic!("expected an arithmetic operation");
+        };
+        assert_eq!(operation.right.span, Span::new(12, 14));
+
+        let source = "println(\n    1\n)";
+        let ExpressionKind::FunctionCall(call) = parse_expression(source) else {
+            panic!("expected a function call");
+        };
+        assert_eq!(call.params[0].span.line_col(source), (2, 5));
+        assert_eq!(parse_spanned(source).span, Span::new(0, source.len()));
+    }
+}
@@ -1,3 +1,33 @@
+use std::{borrow::Cow, fmt};
+
+/// A region of the source code, as byte offsets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// The span that goes from the start of `self` up to the end of `other`
+    pub fn to(self, other: Span) -> Self {
+        Self::new(self.start, other.end)
+    }
+
+    /// The 1-based line and column (in chars) where this span starts in `source`
+    pub fn line_col(&self, source: &str) -> (usize, usize) {
+        let before = &source[..self.start];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        let col = before[line_start..].chars().count() + 1;
+
+        (line, col)
+    }
+}
+
 #[derive(Debug)]
 pub struct CacauProgram<'a> {
     pub items: Vec<HighLevelItem<'a>>,
@@ -6,8 +36,9 @@ pub struct CacauProgram<'a> {
 #[derive(Debug)]
 pub enum HighLevelItem<'a> {
     Fn(Function<'a>),
-    Struct(),
-    Enum(),
+    Struct(Struct<'a>),
+    Enum(Enum<'a>),
+    Impl(Impl<'a>),
     Expr(Expression<'a>),
 }
 
@@ -15,45 +46,284 @@ pub enum HighLevelItem<'a> {
 pub struct Function<'a> {
     pub public: bool,
     pub name: &'a str,
+    /// Whether this is a method that takes `self` before its other arguments
+    pub receiver: bool,
     pub params: Vec<FunctionArgument<'a>>,
-    pub output: &'a str,
+    pub output: Option<&'a str>,
     pub body: Expression<'a>,
+    pub span: Span,
 }
 
 #[derive(Debug)]
 pub struct Struct<'a> {
+    pub public: bool,
     pub name: &'a str,
-    // TODO fields and their types
+    pub fields: Vec<StructField<'a>>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct StructField<'a> {
+    pub public: bool,
+    pub name: &'a str,
+    /// The type as written in the declaration, e.g. `int` or `list<int>`
+    pub type_: &'a str,
+    pub span: Span,
 }
 
 #[derive(Debug)]
 pub struct Enum<'a> {
+    pub public: bool,
     pub name: &'a str,
-    // TODO variants
+    pub variants: Vec<Variant<'a>>,
+    pub span: Span,
 }
 
+/// A variant of an enum, e.g. `Admin(UserData)` or `Ready`
 #[derive(Debug)]
-pub struct Assignment<'a> {
+pub struct Variant<'a> {
     pub name: &'a str,
+    /// The type of the value the variant holds, if it holds one
+    pub payload: Option<&'a str>,
+    pub span: Span,
+}
+
+/// The methods of a type, e.g. `impl Person { fn birthday self { ... } }`
+#[derive(Debug)]
+pub struct Impl<'a> {
+    /// The type that the methods belong to, which `Self` refers to inside them
+    pub ty: &'a str,
+    pub methods: Vec<Function<'a>>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Assignment<'a> {
+    pub pattern: Pattern<'a>,
     pub type_annotation: Option<&'a str>,
     pub expression: Expression<'a>,
 }
 
+/// The names that an assignment or an arm of a `match` binds, e.g. `x` or `(a, (b, c))`
+///
+/// Assignments only bind names and tuples, while the arms of a `match` may also
+/// compare the value with the other patterns.
+#[derive(Debug)]
+pub enum Pattern<'a> {
+    /// Binds any value, except in a `match` where a variant without a value, like `None`,
+    /// is compared with the value instead
+    Identifier(&'a str),
+    /// Binds each item of a tuple with as many items
+    Tuple(Vec<Pattern<'a>>),
+    /// `_`, which matches any value without binding it
+    Wildcard,
+    /// A literal such as `0`, `-1.5` or `"text"`, which matches equal values
+    Literal(Expression<'a>),
+    Variant(VariantPattern<'a>),
+    Struct(StructPattern<'a>),
+}
+
+/// A variant of an enum, with a pattern for its value if it holds one,
+/// e.g. `User::Admin(data)` or `Some(_)`
+#[derive(Debug)]
+pub struct VariantPattern<'a> {
+    /// The enum, unless the variant is written without it
+    pub ty: Option<&'a str>,
+    pub variant: &'a str,
+    pub payload: Option<Box<Pattern<'a>>>,
+}
+
+/// A value of a struct whose fields match their patterns, e.g. `Point { x: 0, y }`
+#[derive(Debug)]
+pub struct StructPattern<'a> {
+    pub name: &'a str,
+    pub fields: Vec<FieldPattern<'a>>,
+    /// Whether the pattern ends with `..`, which allows leaving fields out
+    pub rest: bool,
+}
+
+#[derive(Debug)]
+pub struct FieldPattern<'a> {
+    pub name: &'a str,
+    pub pattern: Pattern<'a>,
+}
+
+/// An anonymous function, e.g. `fn a, b { a + b }`
+#[derive(Debug)]
+pub struct Lambda<'a> {
+    pub params: Vec<&'a str>,
+    pub body: Expression<'a>,
+}
+
+/// Compares a value with the pattern of each arm, evaluating to the body of the first arm
+/// that matches and whose guard holds
+#[derive(Debug)]
+pub struct Match<'a> {
+    pub scrutinee: Expression<'a>,
+    pub arms: Vec<MatchArm<'a>>,
+}
+
+#[derive(Debug)]
+pub struct MatchArm<'a> {
+    pub pattern: Pattern<'a>,
+    /// A condition written after the pattern, as in `n if n > 0 => ...`
+    pub guard: Option<Expression<'a>>,
+    pub body: Expression<'a>,
+    pub span: Span,
+}
+
+/// Changing the value of variables that were already declared, e.g. `a = a + 10`,
+/// `i += 1` or `(a, b) = (b, a)`
+#[derive(Debug)]
+pub struct Reassignment<'a> {
+    pub target: Assignee<'a>,
+    /// The operator of a compound assignment, applied to the current and the assigned value
+    pub op: Option<ArithmeticOperator>,
+    pub expression: Expression<'a>,
+}
+
+/// The variables or fields that a reassignment changes
 #[derive(Debug)]
-pub enum Expression<'a> {
+pub enum Assignee<'a> {
+    Variable(&'a str),
+    /// A field of a struct held by another assignee, e.g. `joao.is_vaccinated`
+    Field {
+        base: Box<Assignee<'a>>,
+        field: &'a str,
+    },
+    /// Assigns each item of a tuple with as many items
+    Tuple(Vec<Assignee<'a>>),
+}
+
+/// Shows the assignee the way it's written, e.g. `(a, joao.age)`
+impl fmt::Display for Assignee<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Assignee::Variable(name) => f.write_str(name),
+            Assignee::Field { base, field } => write!(f, "{}.{}", base, field),
+            Assignee::Tuple(targets) => {
+                f.write_str("(")?;
+                for (idx, target) in targets.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", target)?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Expression<'a> {
+    pub kind: ExpressionKind<'a>,
+    pub span: Span,
+}
+
+impl<'a> Expression<'a> {
+    pub fn new(kind: ExpressionKind<'a>, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// Wraps an expression built by hand, which has no location in the source
+impl<'a> From<ExpressionKind<'a>> for Expression<'a> {
+    fn from(kind: ExpressionKind<'a>) -> Self {
+        Self::new(kind, Span::default())
+    }
+}
+
+#[derive(Debug)]
+pub enum ExpressionKind<'a> {
     Identifier(&'a str),
     Assignment(Box<Assignment<'a>>),
+    Reassignment(Box<Reassignment<'a>>),
     BooleanLiteral(bool),
     IntegerLiteral(i64),
     FloatLiteral(f64),
     CharLiteral(char),
-    StringLiteral(&'a str),
+    StringLiteral(Cow<'a, str>),
+    ListLiteral(Vec<Expression<'a>>),
+    TupleLiteral(Vec<Expression<'a>>),
+    StructLiteral(StructLiteral<'a>),
+    VariantLiteral(Box<VariantLiteral<'a>>),
+    Index(Box<Index<'a>>),
+    FieldAccess(Box<FieldAccess<'a>>),
     FunctionCall(FunctionCall<'a>),
+    MethodCall(Box<MethodCall<'a>>),
     ArithOperation(Box<ArithmeticOperation<'a>>),
     CompOperation(Box<ComparisonOperation<'a>>),
     BoolOperation(Box<BooleanOperation<'a>>),
+    BitOperation(Box<BitwiseOperation<'a>>),
     Not(Box<Expression<'a>>),
     Minus(Box<Expression<'a>>),
+    /// `~x`, which flips every bit of an int
+    BitNot(Box<Expression<'a>>),
+    If(Box<If<'a>>),
+    Match(Box<Match<'a>>),
+    Lambda(Box<Lambda<'a>>),
+    Block(Box<Block<'a>>),
+}
+
+/// A sequence of statements between curly braces, e.g. `{ let x = 2; x * x }`
+#[derive(Debug)]
+pub struct Block<'a> {
+    pub statements: Vec<Expression<'a>>,
+    /// The last expression of the block when it isn't followed by a semicolon,
+    /// which becomes the value of the block
+    pub tail: Option<Expression<'a>>,
+}
+
+/// Accessing an item by its position, e.g. `list[0]` or `tuple[1]`, where negative
+/// positions count from the end
+#[derive(Debug)]
+pub struct Index<'a> {
+    pub indexed: Expression<'a>,
+    pub index: Expression<'a>,
+}
+
+/// A value of a struct, e.g. `Point { x: 1, y: 2 }`
+#[derive(Debug)]
+pub struct StructLiteral<'a> {
+    pub name: &'a str,
+    pub fields: Vec<FieldValue<'a>>,
+}
+
+#[derive(Debug)]
+pub struct FieldValue<'a> {
+    pub name: &'a str,
+    pub expression: Expression<'a>,
+}
+
+/// A variant of an enum named along with its type, e.g. `User::Admin(data)` or `Status::Ready`,
+/// or a call to a function of a type that doesn't take `self`, e.g. `Point::new(1, 2)`
+#[derive(Debug)]
+pub struct VariantLiteral<'a> {
+    pub ty: &'a str,
+    pub name: &'a str,
+    pub params: Vec<Expression<'a>>,
+}
+
+/// Reading a field of a struct, e.g. `joao.age`
+#[derive(Debug)]
+pub struct FieldAccess<'a> {
+    pub base: Expression<'a>,
+    pub field: &'a str,
+}
+
+#[derive(Debug)]
+pub struct If<'a> {
+    pub condition: Expression<'a>,
+    pub then: Expression<'a>,
+    pub elifs: Vec<Elif<'a>>,
+    pub otherwise: Option<Expression<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Elif<'a> {
+    pub condition: Expression<'a>,
+    pub then: Expression<'a>,
 }
 
 #[derive(Debug)]
@@ -77,6 +347,13 @@ pub struct BooleanOperation<'a> {
     pub right: Expression<'a>,
 }
 
+#[derive(Debug)]
+pub struct BitwiseOperation<'a> {
+    pub left: Expression<'a>,
+    pub op: BitwiseOperator,
+    pub right: Expression<'a>,
+}
+
 #[derive(Debug)]
 pub enum ArithmeticOperator {
     Add,
@@ -91,6 +368,18 @@ pub enum ArithmeticOperator {
 pub enum BooleanOperator {
     Or,
     And,
+    Xor,
+    Nand,
+    Nor,
+}
+
+#[derive(Debug)]
+pub enum BitwiseOperator {
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
 }
 
 #[derive(Debug)]
@@ -109,8 +398,17 @@ pub struct FunctionCall<'a> {
     pub params: Vec<Expression<'a>>,
 }
 
+/// Calling a method of a value, e.g. `tuple.get(0)`
+#[derive(Debug)]
+pub struct MethodCall<'a> {
+    pub receiver: Expression<'a>,
+    pub name: &'a str,
+    pub params: Vec<Expression<'a>>,
+}
+
 #[derive(Debug)]
 pub struct FunctionArgument<'a> {
     pub name: &'a str,
     pub type_: &'a str,
+    pub span: Span,
 }
@@ -0,0 +1,97 @@
+//! Functions that every program can call without defining them
+
+use crate::{error::RuntimeError, mem::Value, numbers::float_to_int};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Builtin {
+    /// `list()`, an empty list
+    List,
+    Println,
+    Assert,
+    /// `panic()`, which stops the program
+    Panic,
+    /// `string(value)`, `int(value)`, ...
+    Convert(Conversion),
+}
+
+impl Builtin {
+    /// The builtin function called `name`, if there's one
+    pub fn get(name: &str) -> Option<Self> {
+        let builtin = match name {
+            "list" => Builtin::List,
+            "println" => Builtin::Println,
+            "assert" => Builtin::Assert,
+            "panic" => Builtin::Panic,
+            "string" => Builtin::Convert(Conversion::String),
+            "int" => Builtin::Convert(Conversion::Int),
+            "float" => Builtin::Convert(Conversion::Float),
+            "bool" => Builtin::Convert(Conversion::Bool),
+            _ => return None,
+        };
+
+        Some(builtin)
+    }
+
+    /// The number of arguments that the function takes
+    pub fn arity(self) -> usize {
+        match self {
+            Builtin::List | Builtin::Panic => 0,
+            Builtin::Println | Builtin::Assert | Builtin::Convert(_) => 1,
+        }
+    }
+}
+
+/// An explicit conversion of a value to another type
+#[derive(Debug, Clone, Copy)]
+pub enum Conversion {
+    /// Any value, written the way `println` writes it, so floats keep five decimals
+    String,
+    /// A bool, a float, which is truncated towards zero, or a string of digits
+    Int,
+    /// An int or a string of a number
+    Float,
+    /// An int, which is `true` unless it's zero, or the string `"true"` or `"false"`
+    Bool,
+}
+
+impl Conversion {
+    pub fn name(self) -> &'static str {
+        match self {
+            Conversion::String => "string",
+            Conversion::Int => "int",
+            Conversion::Float => "float",
+            Conversion::Bool => "bool",
+        }
+    }
+
+    pub fn apply(self, value: Value) -> Result<Value, RuntimeError> {
+        let converted = match (self, &value) {
+            (Conversion::String, Value::String(_)) => Some(value.clone()),
+            (Conversion::String, value) => Some(Value::String(value.to_string())),
+
+            (Conversion::Int, Value::Integer(_)) => Some(value.clone()),
+            (Conversion::Int, Value::Boolean(bool)) => Some(Value::Integer(*bool as i64)),
+            (Conversion::Int, Value::Float(float)) => float_to_int(*float).map(Value::Integer),
+            (Conversion::Int, Value::String(string)) => string.parse().ok().map(Value::Integer),
+
+            (Conversion::Float, Value::Float(_)) => Some(value.clone()),
+            (Conversion::Float, Value::Integer(int)) => Some(Value::Float(*int as f64)),
+            (Conversion::Float, Value::String(string)) => string.parse().ok().map(Value::Float),
+
+            (Conversion::Bool, Value::Boolean(_)) => Some(value.clone()),
+            (Conversion::Bool, Value::Integer(int)) => Some(Value::Boolean(*int != 0)),
+            (Conversion::Bool, Value::String(string)) => match string.as_str() {
+                "true" => Some(Value::Boolean(true)),
+                "false" => Some(Value::Boolean(false)),
+                _ => None,
+            },
+
+            _ => None,
+        };
+
+        converted.ok_or(RuntimeError::InvalidConversion {
+            to: self.name(),
+            value,
+        })
+    }
+}
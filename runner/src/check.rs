@@ -0,0 +1,208 @@
+//! Checks done over the whole program before it starts running.
+
+use crate::{
+    ast::{CacauProgram, Expression, ExpressionKind, HighLevelItem, Pattern},
+    enums::Enums,
+    error::{Error, RuntimeError},
+};
+
+type Result<T> = std::result::Result<T, Error>;
+
+pub fn check_program(program: &CacauProgram) -> Result<()> {
+    let mut enums = Enums::new();
+    for item in program.items.iter() {
+        if let HighLevelItem::Enum(enum_) = item {
+            enums.insert(enum_.name, &enum_.variants);
+        }
+    }
+    let enums = &enums;
+
+    for item in program.items.iter() {
+        match item {
+            HighLevelItem::Expr(expr) => check_expr(expr, false, enums)?,
+            // Functions that declare an output type return the value of their body
+            HighLevelItem::Fn(function) => {
+                check_expr(&function.body, function.output.is_some(), enums)?
+            }
+            HighLevelItem::Impl(impl_) => {
+                for method in impl_.methods.iter() {
+                    check_expr(&method.body, method.output.is_some(), enums)?;
+                }
+            }
+            HighLevelItem::Struct(_) | HighLevelItem::Enum(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks `expr`, where `as_value` tells whether its value is used by the surrounding code
+fn check_expr(expr: &Expression, as_value: bool, enums: &Enums) -> Result<()> {
+    use ExpressionKind::*;
+    match &expr.kind {
+        Identifier(_) | BooleanLiteral(_) | IntegerLiteral(_) | FloatLiteral(_)
+        | CharLiteral(_) | StringLiteral(_) => Ok(()),
+        Assignment(assign) => check_expr(&assign.expression, true, enums),
+        Reassignment(reassign) => check_expr(&reassign.expression, true, enums),
+        ListLiteral(items) | TupleLiteral(items) => items
+            .iter()
+            .try_for_each(|item| check_expr(item, true, enums)),
+        Index(index) => {
+            check_expr(&index.indexed, true, enums)?;
+            check_expr(&index.index, true, enums)
+        }
+        FunctionCall(call) => call
+            .params
+            .iter()
+            .try_for_each(|param| check_expr(param, true, enums)),
+        StructLiteral(literal) => literal
+            .fields
+            .iter()
+            .try_for_each(|field| check_expr(&field.expression, true, enums)),
+        VariantLiteral(literal) => literal
+            .params
+            .iter()
+            .try_for_each(|param| check_expr(param, true, enums)),
+        FieldAccess(access) => check_expr(&access.base, true, enums),
+        MethodCall(call) => {
+            check_expr(&call.receiver, true, enums)?;
+            call.params
+                .iter()
+                .try_for_each(|param| check_expr(param, true, enums))
+        }
+        ArithOperation(arith) => {
+            check_expr(&arith.left, true, enums)?;
+            check_expr(&arith.right, true, enums)
+        }
+        CompOperation(comp) => {
+            check_expr(&comp.left, true, enums)?;
+            check_expr(&comp.right, true, enums)
+        }
+        BoolOperation(boolean) => {
+            check_expr(&boolean.left, true, enums)?;
+            check_expr(&boolean.right, true, enums)
+        }
+        BitOperation(bitwise) => {
+            check_expr(&bitwise.left, true, enums)?;
+            check_expr(&bitwise.right, true, enums)
+        }
+        Not(expr) | Minus(expr) | BitNot(expr) => check_expr(expr, true, enums),
+        If(if_) => {
+            // Without an else branch there would be no value when every condition is false
+            if as_value && if_.otherwise.is_none() {
+                return Err(RuntimeError::IfWithoutElse.at(expr.span));
+            }
+
+            check_expr(&if_.condition, true, enums)?;
+            check_expr(&if_.then, as_value, enums)?;
+            for elif in if_.elifs.iter() {
+                check_expr(&elif.condition, true, enums)?;
+                check_expr(&elif.then, as_value, enums)?;
+            }
+            match if_.otherwise {
+                Some(ref otherwise) => check_expr(otherwise, as_value, enums),
+                None => Ok(()),
+            }
+        }
+        // Like functions without an output type, lambdas may end without a value
+        Lambda(lambda) => check_expr(&lambda.body, false, enums),
+        Match(match_) => {
+            check_expr(&match_.scrutinee, true, enums)?;
+            for arm in match_.arms.iter() {
+                if let Some(ref guard) = arm.guard {
+                    check_expr(guard, true, enums)?;
+                }
+                check_expr(&arm.body, as_value, enums)?;
+            }
+
+            // Arms with a guard may not be taken even when their pattern matches
+            let patterns: Vec<_> = match_
+                .arms
+                .iter()
+                .filter(|arm| arm.guard.is_none())
+                .map(|arm| &arm.pattern)
+                .collect();
+            let missing = missing_variants(&patterns, enums);
+            if !missing.is_empty() {
+                return Err(RuntimeError::NonExhaustiveMatch(missing).at(expr.span));
+            }
+
+            Ok(())
+        }
+        Block(block) => {
+            for statement in block.statements.iter() {
+                check_expr(statement, false, enums)?;
+            }
+            match block.tail {
+                Some(ref tail) => check_expr(tail, as_value, enums),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+/// The variants of an enum that `patterns` leave out, written as patterns such as
+/// `Guest` or `Some(Admin(_))`
+///
+/// Only patterns that are all variants of the same enum are checked here. Any
+/// other pattern, such as a name or a literal, is taken to cover every value, and
+/// a value that no arm matches is reported while running instead.
+fn missing_variants(patterns: &[&Pattern], enums: &Enums) -> Vec<String> {
+    let mut ty = None;
+    let mut arms = Vec::with_capacity(patterns.len());
+    for pattern in patterns {
+        let (enum_, variant, payload) = match pattern {
+            Pattern::Variant(pattern) => {
+                let enum_ = match pattern.ty {
+                    Some(ty) => ty,
+                    None => match enums.resolve(pattern.variant) {
+                        Ok(Some((ty, _))) => ty,
+                        _ => return Vec::new(),
+                    },
+                };
+                (enum_, pattern.variant, pattern.payload.as_deref())
+            }
+            // A name that isn't a variant binds any value
+            Pattern::Identifier(name) => match enums.resolve(name) {
+                Ok(Some((ty, _))) => (ty, *name, None),
+                _ => return Vec::new(),
+            },
+            _ => return Vec::new(),
+        };
+
+        if *ty.get_or_insert(enum_) != enum_ {
+            return Vec::new();
+        }
+        arms.push((variant, payload));
+    }
+
+    let Some(variants) = ty.and_then(|ty| enums.get(ty)) else {
+        return Vec::new();
+    };
+
+    let mut missing = Vec::new();
+    for variant in variants {
+        let payloads: Vec<_> = arms
+            .iter()
+            .filter(|(name, _)| *name == variant.name)
+            .map(|(_, payload)| *payload)
+            .collect();
+
+        match (variant.payload, payloads.is_empty()) {
+            (Some(_), true) => missing.push(format!("{}(_)", variant.name)),
+            (None, true) => missing.push(variant.name.to_string()),
+            (Some(_), false) => {
+                // A pattern without a value for a variant that holds one fails while running
+                let Some(payloads) = payloads.into_iter().collect::<Option<Vec<_>>>() else {
+                    continue;
+                };
+                for nested in missing_variants(&payloads, enums) {
+                    missing.push(format!("{}({})", variant.name, nested));
+                }
+            }
+            (None, false) => {}
+        }
+    }
+
+    missing
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use crate::{ast::Variant, error::RuntimeError, prelude};
+
+/// The enums that a program can use, including those of the prelude
+pub struct Enums<'a> {
+    variants_of: HashMap<&'a str, &'a [Variant<'a>]>,
+    /// The enums that declare each variant, to resolve variants written without their type
+    enums_of: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> Enums<'a> {
+    /// The enums of the prelude
+    pub fn new() -> Self {
+        let mut enums = Enums {
+            variants_of: HashMap::new(),
+            enums_of: HashMap::new(),
+        };
+        for &(name, variants) in prelude::ENUMS {
+            enums.insert(name, variants);
+        }
+
+        enums
+    }
+
+    pub fn insert(&mut self, name: &'a str, variants: &'a [Variant<'a>]) {
+        self.variants_of.insert(name, variants);
+        for variant in variants {
+            let enums = self.enums_of.entry(variant.name).or_default();
+            // Variants of the program shadow those of the prelude
+            enums.retain(|enum_| !prelude::ENUMS.iter().any(|(name, _)| name == enum_));
+            enums.push(name);
+        }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.variants_of.contains_key(name)
+    }
+
+    /// The variants of the enum called `name`
+    pub fn get(&self, name: &str) -> Option<&'a [Variant<'a>]> {
+        self.variants_of.get(name).copied()
+    }
+
+    /// The name of the enum called `name`, as it was declared, along with its variants
+    pub fn get_key_value(&self, name: &str) -> Option<(&'a str, &'a [Variant<'a>])> {
+        self.variants_of
+            .get_key_value(name)
+            .map(|(&name, &variants)| (name, variants))
+    }
+
+    /// Finds the enum that declares a variant written without its type, e.g. `Admin(data)`
+    pub fn resolve(&self, name: &str) -> Result<Option<(&'a str, &'a Variant<'a>)>, RuntimeError> {
+        let ty = match self.enums_of.get(name).map(Vec::as_slice) {
+            None | Some([]) => return Ok(None),
+            Some([ty]) => *ty,
+            Some(_) => return Err(RuntimeError::AmbiguousVariant(name.into())),
+        };
+        let variant = self.variants_of[ty]
+            .iter()
+            .find(|variant| variant.name == name)
+            .expect("variants are registered along with their enum");
+
+        Ok(Some((ty, variant)))
+    }
+}
@@ -0,0 +1,353 @@
+use std::fmt;
+
+use crate::{ast::Span, mem::Value};
+
+/// A [`RuntimeError`] along with the span of the expression that raised it
+#[derive(Debug)]
+pub struct Error {
+    pub error: RuntimeError,
+    pub span: Span,
+}
+
+impl Error {
+    /// Renders this error pointing at its location in `source`, which was read from `path`
+    ///
+    /// ```text
+    /// error: assertion failed
+    ///   --> tests/02_comparisons.cau:12:1
+    ///    |
+    /// 12 | assert(text == "bar");
+    ///    | ^^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    pub fn report(&self, path: &str, source: &str) -> String {
+        let (line, col) = self.span.line_col(source);
+        let line_start = source[..self.span.start]
+            .rfind('\n')
+            .map_or(0, |idx| idx + 1);
+        let text = source[line_start..].lines().next().unwrap_or("");
+
+        // Spans over several lines are only underlined until the end of the first one
+        let underlined = source[self.span.start..self.span.end.max(self.span.start)]
+            .lines()
+            .next()
+            .map_or(0, |first_line| first_line.chars().count())
+            .max(1);
+
+        let line_number = line.to_string();
+        let padding = " ".repeat(line_number.len());
+
+        format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+            self.error,
+            padding,
+            path,
+            line,
+            col,
+            padding,
+            line_number,
+            text,
+            padding,
+            " ".repeat(col - 1),
+            "^".repeat(underlined),
+        )
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// An error that stops the execution of a program
+#[derive(Debug)]
+pub enum RuntimeError {
+    /// A binary operator was applied to operands of unsupported types
+    TypeMismatch {
+        op: &'static str,
+        left: Value,
+        right: Value,
+    },
+    /// A unary operator was applied to an operand of an unsupported type
+    InvalidOperand {
+        op: &'static str,
+        value: Value,
+    },
+    /// A value of a specific type was expected, e.g. a boolean in an `if` condition
+    UnexpectedType {
+        expected: &'static str,
+        found: Value,
+    },
+    /// A variable or field was reassigned a value whose type differs from the one it holds
+    ReassignmentTypeMismatch {
+        name: String,
+        expected: &'static str,
+        found: Value,
+    },
+    /// A tuple was taken apart into a different number of items than it holds
+    TupleLengthMismatch {
+        expected: usize,
+        found: Value,
+    },
+    UndefinedVariable(String),
+    UndefinedFunction(String),
+    UndefinedMethod {
+        type_name: String,
+        method: String,
+    },
+    /// A function of a type was called on a value without taking `self`, or on its type
+    /// while taking it
+    ReceiverMismatch {
+        function: String,
+        takes_self: bool,
+    },
+    UndefinedType(String),
+    /// Two types were defined with the same name
+    TypeRedefined(String),
+    /// A field that the struct doesn't declare was given or accessed
+    UndefinedField {
+        struct_name: String,
+        field: String,
+    },
+    /// A struct literal didn't give a value to a field of the struct
+    MissingField {
+        struct_name: String,
+        field: String,
+    },
+    /// A struct literal gave a value to the same field more than once
+    FieldRepeated(String),
+    /// A variant that the enum doesn't declare was named
+    UndefinedVariant {
+        enum_name: String,
+        variant: String,
+    },
+    /// A variant was named without its type, and no enum declares it
+    UnknownVariant(String),
+    /// A variant was named without its type, and more than one enum declares it
+    AmbiguousVariant(String),
+    /// No arm of a `match` matched the value
+    NoMatch(Value),
+    /// The arms of a `match` leave out these variants of an enum
+    NonExhaustiveMatch(Vec<String>),
+    /// A variant was built without a value that it holds, or with one that it doesn't
+    PayloadMismatch {
+        variant: String,
+        expected: bool,
+    },
+    /// A value can't be converted to the type, e.g. by `int("ten")`
+    InvalidConversion {
+        to: &'static str,
+        value: Value,
+    },
+    /// Two functions were defined with the same name
+    FunctionRedefined(String),
+    ArityMismatch {
+        function: String,
+        expected: usize,
+        found: usize,
+    },
+    /// An item was accessed at a position past either end of a list
+    IndexOutOfBounds {
+        index: i64,
+        len: usize,
+    },
+    AssertionFailed,
+    /// An `if` without an `else` branch was used as a value
+    IfWithoutElse,
+    /// Calls or expressions were nested too deeply for the call depth limit or the native stack,
+    /// most likely by unbounded recursion, while calling the given function if any
+    StackOverflow(Option<String>),
+    DivisionByZero {
+        op: &'static str,
+        left: Value,
+    },
+    IntegerOverflow {
+        op: &'static str,
+        left: Value,
+        right: Value,
+    },
+    /// A string or list was repeated a negative number of times
+    NegativeRepetition(i64),
+    /// Repeating a string or list of `len` `count` times would make it longer than `max`
+    RepetitionTooLong {
+        len: usize,
+        count: i64,
+        max: usize,
+    },
+    /// An int was shifted by a negative number of places, or by more than it has bits
+    ShiftOutOfRange {
+        op: &'static str,
+        left: Value,
+        right: Value,
+    },
+    /// The program called `panic()`
+    Panicked,
+    /// Writing to the program's output failed
+    Io(std::io::Error),
+}
+
+impl RuntimeError {
+    /// Attaches the location where this error was raised
+    pub fn at(self, span: Span) -> Error {
+        Error { error: self, span }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use RuntimeError::*;
+
+        match self {
+            TypeMismatch { op, left, right } => write!(
+                f,
+                "operator `{}` cannot be applied to {} and {}",
+                op,
+                Describe(left),
+                Describe(right)
+            ),
+            InvalidOperand { op, value } => write!(
+                f,
+                "operator `{}` cannot be applied to {}",
+                op,
+                Describe(value)
+            ),
+            UnexpectedType { expected, found } => {
+                write!(f, "expected a {}, found {}", expected, Describe(found))
+            }
+            ReassignmentTypeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}` is of type {} and can't be assigned {}",
+                name,
+                expected,
+                Describe(found)
+            ),
+            TupleLengthMismatch { expected, found } => write!(
+                f,
+                "expected a tuple of {} items, found {}",
+                expected,
+                Describe(found)
+            ),
+            UndefinedVariable(name) => write!(f, "variable `{}` is not defined", name),
+            UndefinedFunction(name) => write!(f, "function `{}` is not defined", name),
+            UndefinedMethod { type_name, method } => {
+                write!(f, "type `{}` has no method `{}`", type_name, method)
+            }
+            ReceiverMismatch {
+                function,
+                takes_self: true,
+            } => write!(f, "`{}` takes `self`, so it's called on a value", function),
+            ReceiverMismatch {
+                function,
+                takes_self: false,
+            } => write!(
+                f,
+                "`{}` doesn't take `self`, so it's called on its type",
+                function
+            ),
+            ArityMismatch {
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "function `{}` takes {} argument(s) but {} were supplied",
+                function, expected, found
+            ),
+            InvalidConversion { to, value } => {
+                write!(f, "{} can't be converted to {}", Describe(value), to)
+            }
+            FunctionRedefined(name) => write!(f, "function `{}` is defined more than once", name),
+            UndefinedType(name) => write!(f, "type `{}` is not defined", name),
+            TypeRedefined(name) => write!(f, "type `{}` is defined more than once", name),
+            UndefinedField { struct_name, field } => {
+                write!(f, "struct `{}` has no field `{}`", struct_name, field)
+            }
+            MissingField { struct_name, field } => {
+                write!(f, "missing field `{}` in `{}`", field, struct_name)
+            }
+            FieldRepeated(field) => write!(f, "field `{}` is given more than once", field),
+            UndefinedVariant { enum_name, variant } => {
+                write!(f, "enum `{}` has no variant `{}`", enum_name, variant)
+            }
+            UnknownVariant(variant) => write!(f, "no enum has a variant `{}`", variant),
+            NoMatch(value) => write!(f, "no pattern matches {}", value),
+            NonExhaustiveMatch(missing) => {
+                let missing: Vec<_> = missing
+                    .iter()
+                    .map(|pattern| format!("`{}`", pattern))
+                    .collect();
+                write!(f, "`match` doesn't cover {}", missing.join(", "))
+            }
+            AmbiguousVariant(variant) => write!(
+                f,
+                "more than one enum has a variant `{}`, write it along with its type",
+                variant
+            ),
+            PayloadMismatch {
+                variant,
+                expected: true,
+            } => write!(f, "variant `{}` holds a value, which is missing", variant),
+            PayloadMismatch {
+                variant,
+                expected: false,
+            } => write!(f, "variant `{}` doesn't hold a value", variant),
+            IndexOutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for length {}", index, len)
+            }
+            AssertionFailed => write!(f, "assertion failed"),
+            IfWithoutElse => write!(f, "`if` without an `else` branch can't be used as a value"),
+            StackOverflow(Some(name)) => write!(f, "stack overflow while calling `{}`", name),
+            StackOverflow(None) => write!(f, "stack overflow"),
+            DivisionByZero { op, left } => write!(f, "division by zero in `{} {} 0`", left, op),
+            IntegerOverflow { op, left, right } => {
+                write!(f, "integer overflow in `{} {} {}`", left, op, right)
+            }
+            NegativeRepetition(count) => {
+                write!(
+                    f,
+                    "can't repeat a value a negative number of times ({})",
+                    count
+                )
+            }
+            RepetitionTooLong { len, count, max } => write!(
+                f,
+                "repeating a value of length {} {} times goes over the limit of {}",
+                len, count, max
+            ),
+            ShiftOutOfRange { op, left, right } => write!(
+                f,
+                "shift out of range in `{} {} {}`, ints can only be shifted by 0 to 63 places",
+                left, op, right
+            ),
+            Panicked => write!(f, "the program panicked"),
+            Io(err) => write!(f, "failed to write output: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+impl From<std::io::Error> for RuntimeError {
+    fn from(err: std::io::Error) -> Self {
+        RuntimeError::Io(err)
+    }
+}
+
+/// Shows a value along with its type, quoting strings and chars
+struct Describe<'a>(&'a Value);
+
+impl fmt::Display for Describe<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Value::String(string) => write!(f, "{:?} (string)", string),
+            Value::Char(char) => write!(f, "{:?} (char)", char),
+            value => write!(f, "{} ({})", value, value.type_name()),
+        }
+    }
+}
@@ -1,6 +1,15 @@
+mod builtins;
+mod check;
+mod enums;
+mod error;
+mod numbers;
+mod prelude;
 mod runner;
 
 pub mod ast;
 pub mod mem;
 
-pub use self::runner::Runner;
+pub use self::{
+    error::{Error, RuntimeError},
+    runner::{Limits, Runner},
+};
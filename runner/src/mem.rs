@@ -1,14 +1,49 @@
-use std::collections::HashMap;
+use std::{
+    cell::{RefCell, RefMut},
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+};
 
-pub struct Stack(Vec<u8>);
+use crate::{numbers::compare_int_float, prelude::OPTION};
 
+/// The variables declared in a block, function body or `if` branch
+#[derive(Default)]
 pub struct Scope {
-    pub symbols: Vec<String>,
+    pub symbols: HashMap<String, Variable>,
 }
 
-#[derive(Default)]
+/// Where the value of a variable is kept. Lambdas that use the variable share it, so
+/// changes made by either side are seen by the other
+pub type Variable = Rc<RefCell<Value>>;
+
+/// The variables of a running program, as a stack of nested scopes
+///
+/// The first scope holds the global variables. Each function call starts a new
+/// frame, and code running inside a frame sees the scopes of that frame and the
+/// global scope, but not the scopes of its callers. Calls to lambdas start closed
+/// frames, which don't see the global scope either.
 pub struct SymbolTable {
-    symbols: HashMap<String, Value>,
+    scopes: Vec<Scope>,
+    frames: Vec<Frame>,
+}
+
+/// The scopes of an active function call
+struct Frame {
+    /// Index in `scopes` where the frame starts
+    start: usize,
+    /// Whether code in the frame sees the global variables
+    sees_globals: bool,
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self {
+            scopes: vec![Scope::default()],
+            frames: Vec::new(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -19,14 +54,319 @@ pub enum Value {
     Float(f64),
     Char(char),
     String(String),
+    /// Copies of a list share its items until one of them is changed
+    List(Rc<Vec<Value>>),
+    Tuple(Rc<[Value]>),
+    /// Copies of a struct share its fields until one of them is changed
+    Struct(Rc<StructValue>),
+    /// A variant of an enum, such as the `Some(value)` and `None` of the prelude
+    Enum(Rc<EnumValue>),
+    Function(Rc<Closure>),
+}
+
+/// A value of a struct, with its fields in the order they were declared
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructValue {
+    pub name: String,
+    pub fields: Vec<(String, Value)>,
+}
+
+/// A value of an enum, with the value its variant holds if it holds one
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumValue {
+    pub ty: String,
+    pub variant: String,
+    pub payload: Option<Value>,
+}
+
+/// A function made by a lambda, along with the variables it captured
+#[derive(Clone)]
+pub struct Closure {
+    /// Where the lambda is among the ones that the runner has evaluated
+    pub lambda: usize,
+    /// The variables used by the lambda that were declared when it was evaluated
+    pub captures: Vec<(String, Variable)>,
+    /// The variable that the lambda was declared as, e.g. `f` in `let f = fn { ... }`,
+    /// which refers to the closure itself in its body
+    pub name: Option<String>,
+}
+
+/// Closures are equal when they run the same lambda over the same variables
+impl PartialEq for Closure {
+    fn eq(&self, other: &Self) -> bool {
+        self.lambda == other.lambda
+            && self.name == other.name
+            && self.captures.len() == other.captures.len()
+            && self
+                .captures
+                .iter()
+                .zip(other.captures.iter())
+                .all(|((name1, var1), (name2, var2))| name1 == name2 && Rc::ptr_eq(var1, var2))
+    }
+}
+
+/// Shows the names of the captured variables without their values, which may hold
+/// the closure itself
+impl fmt::Debug for Closure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let captures: Vec<_> = self.captures.iter().map(|(name, _)| name).collect();
+        f.debug_struct("Closure")
+            .field("lambda", &self.lambda)
+            .field("captures", &captures)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl StructValue {
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut Value> {
+        self.fields
+            .iter_mut()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl Value {
+    /// The name of this value's type, as written in Cacau programs, or its kind
+    /// for user-defined types
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Void => "void",
+            Value::Boolean(_) => "bool",
+            Value::Integer(_) => "int",
+            Value::Float(_) => "float",
+            Value::Char(_) => "char",
+            Value::String(_) => "string",
+            Value::List(_) => "list",
+            Value::Tuple(_) => "tuple",
+            Value::Struct(_) => "struct",
+            Value::Enum(_) => "enum",
+            Value::Function(_) => "function",
+        }
+    }
+
+    /// `Some(value)`, from the `Option` of the prelude
+    pub fn some(value: Value) -> Self {
+        Value::Enum(Rc::new(EnumValue {
+            ty: OPTION.into(),
+            variant: "Some".into(),
+            payload: Some(value),
+        }))
+    }
+
+    /// `None`, from the `Option` of the prelude
+    pub fn none() -> Self {
+        Value::Enum(Rc::new(EnumValue {
+            ty: OPTION.into(),
+            variant: "None".into(),
+            payload: None,
+        }))
+    }
+}
+
+/// Values of different types are different, except for ints and floats that hold the same number
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        use Value::*;
+        match (self, other) {
+            (Void, Void) => true,
+            (Boolean(val1), Boolean(val2)) => val1 == val2,
+            (Integer(val1), Integer(val2)) => val1 == val2,
+            (Float(val1), Float(val2)) => val1 == val2,
+            (Integer(int), Float(float)) | (Float(float), Integer(int)) => {
+                compare_int_float(*int, *float) == Some(Ordering::Equal)
+            }
+            (Char(val1), Char(val2)) => val1 == val2,
+            (String(val1), String(val2)) => val1 == val2,
+            (List(val1), List(val2)) => val1 == val2,
+            (Tuple(val1), Tuple(val2)) => val1 == val2,
+            (Struct(val1), Struct(val2)) => val1 == val2,
+            (Enum(val1), Enum(val2)) => val1 == val2,
+            (Function(val1), Function(val2)) => val1 == val2,
+            _ => false,
+        }
+    }
+}
+
+/// Values of different types are unordered, except for ints and floats, and lists and tuples
+/// are ordered lexicographically. Structs, enums and functions are only ordered with the
+/// values they're equal to
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        use Value::*;
+        match (self, other) {
+            (Void, Void) => Some(Ordering::Equal),
+            (Boolean(val1), Boolean(val2)) => val1.partial_cmp(val2),
+            (Integer(val1), Integer(val2)) => val1.partial_cmp(val2),
+            (Float(val1), Float(val2)) => val1.partial_cmp(val2),
+            (Integer(int), Float(float)) => compare_int_float(*int, *float),
+            (Float(float), Integer(int)) => compare_int_float(*int, *float).map(Ordering::reverse),
+            (Char(val1), Char(val2)) => val1.partial_cmp(val2),
+            (String(val1), String(val2)) => val1.partial_cmp(val2),
+            (List(val1), List(val2)) => val1.partial_cmp(val2),
+            (Tuple(val1), Tuple(val2)) => val1.partial_cmp(val2),
+            _ => (self == other).then_some(Ordering::Equal),
+        }
+    }
+}
+
+/// Formats values the way `println` shows them
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Void => write!(f, "()"),
+            Value::Boolean(val) => write!(f, "{}", val),
+            Value::Integer(val) => write!(f, "{}", val),
+            Value::Float(val) => write!(f, "{:.5}", val),
+            Value::Char(val) => write!(f, "{}", val),
+            Value::String(val) => write!(f, "{}", val),
+            Value::List(items) => write_items(f, "[", items, "]"),
+            // A trailing comma tells a tuple of one item apart from a parenthesized value
+            Value::Tuple(items) if items.len() == 1 => write_items(f, "(", items, ",)"),
+            Value::Tuple(items) => write_items(f, "(", items, ")"),
+            Value::Enum(enum_) => match enum_.payload {
+                Some(ref payload) => {
+                    write!(f, "{}(", enum_.variant)?;
+                    write_item(f, payload)?;
+                    f.write_str(")")
+                }
+                None => write!(f, "{}", enum_.variant),
+            },
+            Value::Function(_) => write!(f, "<function>"),
+            Value::Struct(struct_) => {
+                write!(f, "{} {{", struct_.name)?;
+                for (idx, (name, value)) in struct_.fields.iter().enumerate() {
+                    let separator = if idx > 0 { "," } else { "" };
+                    write!(f, "{} {}: ", separator, name)?;
+                    write_item(f, value)?;
+                }
+                f.write_str(" }")
+            }
+        }
+    }
+}
+
+/// Writes `items` separated by commas
+fn write_items(
+    f: &mut fmt::Formatter<'_>,
+    open: &str,
+    items: &[Value],
+    close: &str,
+) -> fmt::Result {
+    f.write_str(open)?;
+    for (idx, item) in items.iter().enumerate() {
+        if idx > 0 {
+            f.write_str(", ")?;
+        }
+        write_item(f, item)?;
+    }
+    f.write_str(close)
+}
+
+/// Writes a value contained in another, quoting it if it's a string or char
+fn write_item(f: &mut fmt::Formatter<'_>, item: &Value) -> fmt::Result {
+    match item {
+        Value::String(string) => write!(f, "{:?}", string),
+        Value::Char(char) => write!(f, "{:?}", char),
+        item => write!(f, "{}", item),
+    }
 }
 
 impl SymbolTable {
+    /// Declares a variable in the innermost scope, shadowing any other with the same name
     pub fn create_var(&mut self, name: &str, value: Value) {
-        self.symbols.insert(name.into(), value);
+        self.share_var(name, Rc::new(RefCell::new(value)));
+    }
+
+    /// Declares a variable in the innermost scope that shares where its value is kept
+    /// with another
+    pub fn share_var(&mut self, name: &str, variable: Variable) {
+        self.innermost().symbols.insert(name.into(), variable);
+    }
+
+    /// The variable that `name` refers to from the innermost scope
+    pub fn get_var(&self, name: &str) -> Option<&Variable> {
+        self.visible_scopes()
+            .find_map(|scope| scope.symbols.get(name))
+    }
+
+    pub fn get_value(&self, name: &str) -> Option<Value> {
+        self.get_var(name).map(|variable| variable.borrow().clone())
+    }
+
+    /// The value of the variable that `name` refers to, to be changed in place
+    pub fn get_value_mut(&self, name: &str) -> Option<RefMut<'_, Value>> {
+        self.get_var(name).map(|variable| variable.borrow_mut())
+    }
+
+    pub fn push_scope(&mut self) {
+        self.scopes.push(Scope::default());
+    }
+
+    /// Drops the innermost scope along with its variables
+    pub fn pop_scope(&mut self) {
+        assert!(
+            self.scopes.len() > self.frame_start().max(1),
+            "popped a scope that belongs to the global scope or another frame"
+        );
+        self.scopes.pop();
+    }
+
+    /// Starts the frame of a function call, with an empty scope for its arguments
+    pub fn push_frame(&mut self) {
+        self.frames.push(Frame {
+            start: self.scopes.len(),
+            sees_globals: true,
+        });
+        self.push_scope();
+    }
+
+    /// Starts the frame of a call to a lambda, which only sees the variables declared in it
+    pub fn push_closed_frame(&mut self) {
+        self.frames.push(Frame {
+            start: self.scopes.len(),
+            sees_globals: false,
+        });
+        self.push_scope();
     }
 
-    pub fn get_value(&self, name: &str) -> Option<&Value> {
-        self.symbols.get(name)
+    /// Drops every scope of the innermost frame
+    pub fn pop_frame(&mut self) {
+        let frame = self.frames.pop().expect("no frame to pop");
+        self.scopes.truncate(frame.start);
+    }
+
+    fn frame_start(&self) -> usize {
+        self.frames.last().map_or(0, |frame| frame.start)
+    }
+
+    fn sees_globals(&self) -> bool {
+        self.frames.last().is_none_or(|frame| frame.sees_globals)
+    }
+
+    fn innermost(&mut self) -> &mut Scope {
+        self.scopes
+            .last_mut()
+            .expect("the global scope is never popped")
+    }
+
+    /// The scopes visible from the innermost one, from the inside out
+    fn visible_scopes(&self) -> impl Iterator<Item = &Scope> {
+        let start = self.frame_start();
+        let globals = if start > 0 && self.sees_globals() {
+            Some(&self.scopes[0])
+        } else {
+            None
+        };
+
+        self.scopes[start..].iter().rev().chain(globals)
     }
 }
@@ -0,0 +1,38 @@
+//! How ints and floats relate to each other, when compared or converted
+
+use std::cmp::Ordering;
+
+/// How `int` compares to `float`, which is exact even for ints that a float can't hold,
+/// unlike comparing `int as f64`. NaN is unordered, so it's neither equal, less nor greater
+pub fn compare_int_float(int: i64, float: f64) -> Option<Ordering> {
+    if float.is_nan() {
+        return None;
+    } else if !in_int_range(float) {
+        return Some(if float < 0.0 {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        });
+    }
+
+    // The integer part of `float` fits in an int, and when it's equal to `int`,
+    // the fractional part tells them apart
+    let integer_part = float.trunc();
+    let ordering = int.cmp(&(integer_part as i64)).then_with(|| {
+        0.0.partial_cmp(&(float - integer_part))
+            .unwrap_or(Ordering::Equal)
+    });
+    Some(ordering)
+}
+
+/// `float` without its fractional part, unless it's out of the range of an int or NaN
+pub fn float_to_int(float: f64) -> Option<i64> {
+    in_int_range(float).then_some(float as i64)
+}
+
+/// Whether the integer part of `float` fits in an int, which is never the case for NaN
+fn in_int_range(float: f64) -> bool {
+    // -2^63 is exactly representable, unlike i64::MAX
+    let min = i64::MIN as f64;
+    float >= min && float < -min
+}
@@ -0,0 +1,23 @@
+//! Types that every program can use without defining them
+
+use crate::ast::{Span, Variant};
+
+/// The name of `enum Option { Some(T), None }`, which accessors such as
+/// `tuple.get(i)` return
+pub const OPTION: &str = "Option";
+
+pub const OPTION_VARIANTS: &[Variant<'static>] = &[
+    Variant {
+        name: "Some",
+        payload: Some("T"),
+        span: Span { start: 0, end: 0 },
+    },
+    Variant {
+        name: "None",
+        payload: None,
+        span: Span { start: 0, end: 0 },
+    },
+];
+
+/// The enums of the prelude, by name
+pub const ENUMS: &[(&str, &[Variant<'static>])] = &[(OPTION, OPTION_VARIANTS)];
@@ -1,373 +1,1963 @@
-use std::io::Write;
+use std::{collections::HashMap, io::Write, rc::Rc};
 
 use crate::{
     ast::{
-        ArithmeticOperation, Assignment, BooleanOperation, CacauProgram, ComparisonOperation,
-        Expression, FunctionCall, HighLevelItem,
+        ArithmeticOperation, ArithmeticOperator, Assignee, Assignment, BitwiseOperation, Block,
+        BooleanOperation, CacauProgram, ComparisonOperation, Expression, ExpressionKind,
+        FieldAccess, Function, FunctionCall, HighLevelItem, If, Index, Lambda, Match, MatchArm,
+        MethodCall, Pattern, Reassignment, Span, Struct, StructLiteral, StructPattern, Variant,
+        VariantLiteral,
     },
-    mem::{SymbolTable, Value},
+    builtins::Builtin,
+    check::check_program,
+    enums::Enums,
+    error::{Error, RuntimeError},
+    mem::{Closure, EnumValue, StructValue, SymbolTable, Value, Variable},
+    numbers::compare_int_float,
 };
 
+type Result<T> = std::result::Result<T, Error>;
+
+/// The native stack left unused below [`Limits::stack_size`], for the frames that run
+/// between two checks of how much of it evaluation has taken
+const STACK_RED_ZONE: usize = 256 * 1024;
+
+/// Bounds on what a program may do, so that a script can't exhaust memory
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// How long a string, in bytes, or a list may get by repetition, e.g. `"ab" * 3`
+    pub max_repeat_len: usize,
+    /// How deep calls to functions and lambdas may nest
+    pub max_call_depth: usize,
+    /// The size of the native stack of the thread that runs the program. Nested calls and
+    /// expressions take some of it each, and evaluation stops with
+    /// [`RuntimeError::StackOverflow`] before it runs out
+    pub stack_size: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_repeat_len: 1 << 20,
+            max_call_depth: 1000,
+            stack_size: 64 << 20,
+        }
+    }
+}
+
 pub struct Runner<'a> {
     stdout: &'a mut dyn Write,
     symbol_table: SymbolTable,
+    functions: HashMap<&'a str, &'a Function<'a>>,
+    structs: HashMap<&'a str, &'a Struct<'a>>,
+    enums: Enums<'a>,
+    /// The methods of each type, by the name of the type
+    methods: HashMap<&'a str, HashMap<&'a str, &'a Function<'a>>>,
+    /// The lambdas that were evaluated, where their closures find them
+    lambdas: Vec<&'a Lambda<'a>>,
+    /// The type that `Self` refers to, inside methods
+    self_type: Option<&'a str>,
+    /// The names of the functions and lambdas being called, innermost last
+    calls: Vec<&'a str>,
+    /// Where the native stack was when the program started running
+    stack_start: usize,
+    limits: Limits,
+}
+
+/// Where a value is stored, to be changed in place: a variable, or a field or an item
+/// of one
+enum Place<'a> {
+    Variable(&'a str),
+    Field(Box<Place<'a>>, &'a str),
+    Item(Box<Place<'a>>, i64),
 }
 
 impl<'a> Runner<'a> {
-    pub fn run(program: &CacauProgram, stdout: &'a mut dyn Write) {
+    pub fn run(program: &'a CacauProgram<'a>, stdout: &'a mut dyn Write) -> Result<()> {
+        Self::run_with_limits(program, stdout, Limits::default())
+    }
+
+    /// Runs `program` within `limits` instead of the default ones
+    pub fn run_with_limits(
+        program: &'a CacauProgram<'a>,
+        stdout: &'a mut dyn Write,
+        limits: Limits,
+    ) -> Result<()> {
+        check_program(program)?;
+
         let mut runner = Runner {
             stdout,
             symbol_table: SymbolTable::default(),
+            functions: HashMap::new(),
+            structs: HashMap::new(),
+            enums: Enums::new(),
+            methods: HashMap::new(),
+            lambdas: Vec::new(),
+            self_type: None,
+            calls: Vec::new(),
+            stack_start: stack_position(),
+            limits,
         };
 
+        // Functions and types are registered up front so they can be used before their definition
+        for item in program.items.iter() {
+            match item {
+                HighLevelItem::Fn(function) => {
+                    if runner.functions.insert(function.name, function).is_some() {
+                        let err = RuntimeError::FunctionRedefined(function.name.into());
+                        return Err(err.at(function.span));
+                    }
+                }
+                HighLevelItem::Struct(struct_) => {
+                    if runner.is_type(struct_.name) {
+                        let err = RuntimeError::TypeRedefined(struct_.name.into());
+                        return Err(err.at(struct_.span));
+                    }
+                    runner.structs.insert(struct_.name, struct_);
+                }
+                HighLevelItem::Enum(enum_) => {
+                    if runner.is_type(enum_.name) {
+                        let err = RuntimeError::TypeRedefined(enum_.name.into());
+                        return Err(err.at(enum_.span));
+                    }
+                    runner.enums.insert(enum_.name, &enum_.variants);
+                }
+                HighLevelItem::Impl(impl_) => {
+                    for method in impl_.methods.iter() {
+                        let methods = runner.methods.entry(impl_.ty).or_default();
+                        if methods.insert(method.name, method).is_some() {
+                            let name = format!("{}.{}", impl_.ty, method.name);
+                            return Err(RuntimeError::FunctionRedefined(name).at(method.span));
+                        }
+                    }
+                }
+                HighLevelItem::Expr(_) => {}
+            }
+        }
+
         for item in program.items.iter() {
             use HighLevelItem::*;
             match item {
                 Expr(ref expr) => {
-                    runner.eval_expr(expr);
+                    runner.eval_expr(expr)?;
+                }
+                Impl(impl_) if !runner.is_type(impl_.ty) => {
+                    return Err(RuntimeError::UndefinedType(impl_.ty.into()).at(impl_.span));
                 }
-                _ => todo!(),
+                Fn(_) | Impl(_) | Struct(_) | Enum(_) => {}
             }
         }
+
+        Ok(())
+    }
+
+    fn is_type(&self, name: &str) -> bool {
+        self.structs.contains_key(name) || self.enums.contains(name)
+    }
+
+    /// Arms hand their work to other methods, since every nested expression and call adds
+    /// a frame of this function to the stack
+    fn eval_expr(&mut self, expr: &'a Expression<'a>) -> Result<Value> {
+        use ExpressionKind::*;
+        let span = expr.span;
+        self.check_stack(span)?;
+        match &expr.kind {
+            FunctionCall(call) => self.eval_function_call(call, span),
+            MethodCall(call) => self.eval_method_call(call, span),
+            IntegerLiteral(integer) => Ok(Value::Integer(*integer)),
+            FloatLiteral(float) => Ok(Value::Float(*float)),
+            BooleanLiteral(boolean) => Ok(Value::Boolean(*boolean)),
+            CharLiteral(char) => Ok(Value::Char(*char)),
+            StringLiteral(string) => Ok(Value::String(string.to_string())),
+            ListLiteral(items) => Ok(Value::List(Rc::new(self.eval_items(items)?))),
+            TupleLiteral(items) => Ok(Value::Tuple(self.eval_items(items)?.into())),
+            StructLiteral(literal) => self.eval_struct_literal(literal, span),
+            VariantLiteral(literal) => self.eval_variant_literal(literal, span),
+            FieldAccess(access) => self.eval_field_access(access, span),
+            Index(index) => self.eval_indexing(index, span),
+            Assignment(assign) => self.eval_assignment(assign, span),
+            Reassignment(reassign) => self.eval_reassignment(reassign, span),
+            Identifier(name) => self.eval_identifier(name).map_err(|err| err.at(span)),
+            CompOperation(comp) => self.eval_comparison_oper(comp, span),
+            ArithOperation(arith) => self.eval_arithmetic_oper(arith, span),
+            BoolOperation(boolean) => self.eval_boolean_oper(boolean, span),
+            BitOperation(bitwise) => self.eval_bitwise_oper(bitwise, span),
+            Not(expr) => self.eval_unary_oper(eval_not, expr, span),
+            Minus(expr) => self.eval_unary_oper(eval_minus, expr, span),
+            BitNot(expr) => self.eval_unary_oper(eval_bit_not, expr, span),
+            If(if_) => self.eval_if(if_),
+            Match(match_) => self.eval_match(match_, span),
+            Lambda(lambda) => Ok(self.eval_lambda(lambda, None)),
+            Block(block) => self.eval_block(block),
+        }
+    }
+
+    /// Fails once evaluation has taken the native stack down to its red zone
+    fn check_stack(&self, span: Span) -> Result<()> {
+        let used = self.stack_start.abs_diff(stack_position());
+        if used > self.limits.stack_size.saturating_sub(STACK_RED_ZONE) {
+            let name = self.calls.last().map(|name| name.to_string());
+            return Err(RuntimeError::StackOverflow(name).at(span));
+        }
+
+        Ok(())
+    }
+
+    fn eval_items(&mut self, items: &'a [Expression<'a>]) -> Result<Vec<Value>> {
+        items.iter().map(|item| self.eval_expr(item)).collect()
+    }
+
+    fn eval_field_access(&mut self, access: &'a FieldAccess<'a>, span: Span) -> Result<Value> {
+        let base = self.eval_expr(&access.base)?;
+        field_of(&base, access.field)
+            .cloned()
+            .map_err(|err| err.at(span))
+    }
+
+    fn eval_indexing(&mut self, index: &'a Index<'a>, span: Span) -> Result<Value> {
+        let indexed = self.eval_expr(&index.indexed)?;
+        let position = self.eval_expr(&index.index)?;
+        eval_index(indexed, position).map_err(|err| err.at(span))
     }
 
-    fn eval_expr(&mut self, expr: &Expression) -> Value {
-        use Expression::*;
-        match expr {
-            FunctionCall(call) => self.eval_function_call(call),
-            IntegerLiteral(integer) => Value::Integer(*integer),
-            FloatLiteral(float) => Value::Float(*float),
-            BooleanLiteral(boolean) => Value::Boolean(*boolean),
-            CharLiteral(char) => Value::Char(*char),
-            StringLiteral(string) => Value::String(String::from(*string)),
-            Assignment(assign) => self.eval_assignment(assign),
-            Identifier(name) => self.eval_identifier(name),
-            CompOperation(comp) => self.eval_comparison_oper(comp),
-            ArithOperation(arith) => self.eval_arithmetic_oper(arith),
-            BoolOperation(boolean) => self.eval_boolean_oper(boolean),
-            Not(expr) => eval_not(self.eval_expr(expr)),
-            Minus(expr) => eval_minus(self.eval_expr(expr)),
+    fn eval_unary_oper(
+        &mut self,
+        oper: fn(Value) -> std::result::Result<Value, RuntimeError>,
+        expr: &'a Expression<'a>,
+        span: Span,
+    ) -> Result<Value> {
+        let value = self.eval_expr(expr)?;
+        oper(value).map_err(|err| err.at(span))
+    }
+
+    /// Builds a value of a declared struct, which must be given every field it declares
+    fn eval_struct_literal(&mut self, literal: &'a StructLiteral<'a>, span: Span) -> Result<Value> {
+        let name = match (literal.name, self.self_type) {
+            ("Self", Some(self_type)) => self_type,
+            (name, _) => name,
+        };
+        let declaration = *self
+            .structs
+            .get(name)
+            .ok_or_else(|| RuntimeError::UndefinedType(name.into()).at(span))?;
+
+        let names: Vec<_> = literal.fields.iter().map(|field| field.name).collect();
+        check_fields(declaration, &names, true).map_err(|err| err.at(span))?;
+
+        let mut fields = Vec::with_capacity(declaration.fields.len());
+        for decl in declaration.fields.iter() {
+            let field = literal
+                .fields
+                .iter()
+                .find(|field| field.name == decl.name)
+                .expect("fields are checked above");
+            fields.push((decl.name.to_string(), self.eval_expr(&field.expression)?));
+        }
+
+        Ok(Value::Struct(Rc::new(StructValue {
+            name: name.into(),
+            fields,
+        })))
+    }
+
+    /// Calls a function of a type that doesn't take `self`, e.g. `Point::new(1, 2)`, or
+    /// builds a variant of a declared enum, e.g. `User::Admin(data)`
+    fn eval_variant_literal(
+        &mut self,
+        literal: &'a VariantLiteral<'a>,
+        span: Span,
+    ) -> Result<Value> {
+        let ty = match (literal.ty, self.self_type) {
+            ("Self", Some(self_type)) => self_type,
+            (ty, _) => ty,
+        };
+
+        if let Some((ty, function)) = self.method_of(ty, literal.name) {
+            if function.receiver {
+                return Err(RuntimeError::ReceiverMismatch {
+                    function: format!("{}::{}", ty, function.name),
+                    takes_self: true,
+                }
+                .at(span));
+            }
+            return self.eval_user_function_call(function, Some(ty), &literal.params, span);
+        }
+        if self.structs.contains_key(ty) {
+            return Err(RuntimeError::UndefinedMethod {
+                type_name: ty.into(),
+                method: literal.name.into(),
+            }
+            .at(span));
+        }
+
+        let (ty, variant) = self
+            .variant_of(Some(ty), literal.name)
+            .map_err(|err| err.at(span))?;
+
+        let payload = literal.params.iter().collect::<Vec<_>>();
+        self.eval_variant(ty, variant, &payload, span)
+    }
+
+    /// The function called `name` in the `impl` of `ty`, along with the name of the type
+    fn method_of(&self, ty: &str, name: &str) -> Option<(&'a str, &'a Function<'a>)> {
+        let (&ty, methods) = self.methods.get_key_value(ty)?;
+        Some((ty, *methods.get(name)?))
+    }
+
+    /// The declaration of a variant of the enum `ty`, or of the enum that declares it
+    /// when it's written without its type
+    fn variant_of(
+        &self,
+        ty: Option<&str>,
+        name: &str,
+    ) -> std::result::Result<(&'a str, &'a Variant<'a>), RuntimeError> {
+        let ty = match (ty, self.self_type) {
+            (Some("Self"), Some(self_type)) => self_type,
+            (Some(ty), _) => ty,
+            (None, _) => {
+                return self
+                    .enums
+                    .resolve(name)?
+                    .ok_or_else(|| RuntimeError::UnknownVariant(name.into()))
+            }
+        };
+        let (ty, variants) = self
+            .enums
+            .get_key_value(ty)
+            .ok_or_else(|| RuntimeError::UndefinedType(ty.into()))?;
+        let variant = variants
+            .iter()
+            .find(|variant| variant.name == name)
+            .ok_or_else(|| RuntimeError::UndefinedVariant {
+                enum_name: ty.into(),
+                variant: name.into(),
+            })?;
+
+        Ok((ty, variant))
+    }
+
+    /// Builds a variant of the enum `ty`, which must be given a value if and only if it holds one
+    fn eval_variant(
+        &mut self,
+        ty: &str,
+        variant: &Variant,
+        payload: &[&'a Expression<'a>],
+        span: Span,
+    ) -> Result<Value> {
+        let payload = match (variant.payload, payload) {
+            (Some(_), [value]) => Some(self.eval_expr(value)?),
+            (None, []) => None,
+            (expected, _) => {
+                return Err(RuntimeError::PayloadMismatch {
+                    variant: variant.name.into(),
+                    expected: expected.is_some(),
+                }
+                .at(span))
+            }
+        };
+
+        Ok(Value::Enum(Rc::new(EnumValue {
+            ty: ty.into(),
+            variant: variant.name.into(),
+            payload,
+        })))
+    }
+
+    /// Evaluates to the value of the first branch whose condition holds, or of the `else` branch
+    fn eval_if(&mut self, if_: &'a If<'a>) -> Result<Value> {
+        let branches = std::iter::once((&if_.condition, &if_.then))
+            .chain(if_.elifs.iter().map(|elif| (&elif.condition, &elif.then)));
+
+        for (condition, then) in branches {
+            match self.eval_expr(condition)? {
+                Value::Boolean(true) => return self.eval_in_scope(then),
+                Value::Boolean(false) => {}
+                found => {
+                    return Err(RuntimeError::UnexpectedType {
+                        expected: "bool",
+                        found,
+                    }
+                    .at(condition.span))
+                }
+            }
+        }
+
+        match if_.otherwise {
+            Some(ref otherwise) => self.eval_in_scope(otherwise),
+            None => Ok(Value::Void),
+        }
+    }
+
+    /// Evaluates `expr` in a new scope, dropping the variables it declares afterwards
+    fn eval_in_scope(&mut self, expr: &'a Expression<'a>) -> Result<Value> {
+        self.symbol_table.push_scope();
+        let result = self.eval_expr(expr);
+        self.symbol_table.pop_scope();
+
+        result
+    }
+
+    /// Evaluates the statements of `block` in a new scope, yielding the value of its tail
+    fn eval_block(&mut self, block: &'a Block<'a>) -> Result<Value> {
+        self.symbol_table.push_scope();
+        let result = self.eval_statements(block);
+        self.symbol_table.pop_scope();
+
+        result
+    }
+
+    fn eval_statements(&mut self, block: &'a Block<'a>) -> Result<Value> {
+        for statement in block.statements.iter() {
+            self.eval_expr(statement)?;
+        }
+
+        match block.tail {
+            Some(ref tail) => self.eval_expr(tail),
+            None => Ok(Value::Void),
         }
     }
 
     // TODO assignment returns the assigned value?
-    // TODO scope rules
-    fn eval_assignment(&mut self, assign: &Assignment) -> Value {
-        let val = self.eval_expr(&assign.expression);
-        self.symbol_table.create_var(assign.name, val);
+    fn eval_assignment(&mut self, assign: &'a Assignment<'a>, span: Span) -> Result<Value> {
+        let val = match (&assign.pattern, &assign.expression.kind) {
+            (Pattern::Identifier(name), ExpressionKind::Lambda(lambda)) => {
+                self.eval_lambda(lambda, Some(name))
+            }
+            _ => self.eval_expr(&assign.expression)?,
+        };
+        self.bind(&assign.pattern, val, span)?;
+
+        Ok(Value::Void)
+    }
+
+    /// Declares the variables of `pattern`, taking `value` apart to match it
+    fn bind(&mut self, pattern: &'a Pattern<'a>, value: Value, span: Span) -> Result<()> {
+        match pattern {
+            Pattern::Identifier(name) => self.symbol_table.create_var(name, value),
+            Pattern::Tuple(patterns) => {
+                let items = untuple(value, patterns.len()).map_err(|err| err.at(span))?;
+                for (pattern, value) in patterns.iter().zip(items) {
+                    self.bind(pattern, value, span)?;
+                }
+            }
+            // Only a `match` can fall back to another pattern
+            _ => {
+                if !self.matches(pattern, &value, span)? {
+                    return Err(RuntimeError::NoMatch(value).at(span));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evaluates the body of the first arm whose pattern matches the value and whose guard holds
+    ///
+    /// Each arm has its own scope, where the variables of its pattern are declared.
+    fn eval_match(&mut self, match_: &'a Match<'a>, span: Span) -> Result<Value> {
+        let value = self.eval_expr(&match_.scrutinee)?;
+
+        for arm in match_.arms.iter() {
+            self.symbol_table.push_scope();
+            let result = self.eval_arm(arm, &value);
+            self.symbol_table.pop_scope();
+
+            if let Some(result) = result.transpose() {
+                return result;
+            }
+        }
+
+        Err(RuntimeError::NoMatch(value).at(span))
+    }
+
+    /// The value of the body of `arm`, unless the arm doesn't match `value`
+    fn eval_arm(&mut self, arm: &'a MatchArm<'a>, value: &Value) -> Result<Option<Value>> {
+        if !self.matches(&arm.pattern, value, arm.span)? {
+            return Ok(None);
+        }
+
+        if let Some(ref guard) = arm.guard {
+            match self.eval_expr(guard)? {
+                Value::Boolean(true) => {}
+                Value::Boolean(false) => return Ok(None),
+                found => {
+                    return Err(RuntimeError::UnexpectedType {
+                        expected: "bool",
+                        found,
+                    }
+                    .at(guard.span))
+                }
+            }
+        }
+
+        self.eval_expr(&arm.body).map(Some)
+    }
+
+    /// Whether `value` matches `pattern`, declaring the variables the pattern binds
+    fn matches(&mut self, pattern: &'a Pattern<'a>, value: &Value, span: Span) -> Result<bool> {
+        match pattern {
+            Pattern::Wildcard => Ok(true),
+            Pattern::Identifier(name) => {
+                match self.enums.resolve(name).map_err(|err| err.at(span))? {
+                    // Variants without a value, like `None`, are compared instead of bound
+                    Some((ty, variant)) => self.matches_variant(ty, variant, None, value, span),
+                    None => {
+                        self.symbol_table.create_var(name, value.clone());
+                        Ok(true)
+                    }
+                }
+            }
+            Pattern::Tuple(patterns) => {
+                let items = untuple(value.clone(), patterns.len()).map_err(|err| err.at(span))?;
+                for (pattern, item) in patterns.iter().zip(items.iter()) {
+                    if !self.matches(pattern, item, span)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Pattern::Literal(literal) => {
+                let literal = self.eval_expr(literal)?;
+                let equals = eval_equals(value.clone(), literal).map_err(|err| err.at(span))?;
+                Ok(matches!(equals, Value::Boolean(true)))
+            }
+            Pattern::Variant(pattern) => {
+                let (ty, variant) = self
+                    .variant_of(pattern.ty, pattern.variant)
+                    .map_err(|err| err.at(span))?;
+                self.matches_variant(ty, variant, pattern.payload.as_deref(), value, span)
+            }
+            Pattern::Struct(pattern) => self.matches_struct(pattern, value, span),
+        }
+    }
+
+    /// Whether `value` is the variant `variant` of the enum `ty`, holding a value that
+    /// matches `payload`
+    fn matches_variant(
+        &mut self,
+        ty: &str,
+        variant: &Variant,
+        payload: Option<&'a Pattern<'a>>,
+        value: &Value,
+        span: Span,
+    ) -> Result<bool> {
+        if variant.payload.is_some() != payload.is_some() {
+            return Err(RuntimeError::PayloadMismatch {
+                variant: variant.name.into(),
+                expected: variant.payload.is_some(),
+            }
+            .at(span));
+        }
+
+        let Value::Enum(enum_) = value else {
+            return Err(RuntimeError::UnexpectedType {
+                expected: "enum",
+                found: value.clone(),
+            }
+            .at(span));
+        };
+        if enum_.ty != ty || enum_.variant != variant.name {
+            return Ok(false);
+        }
+
+        match (payload, &enum_.payload) {
+            (Some(pattern), Some(value)) => self.matches(pattern, value, span),
+            _ => Ok(true),
+        }
+    }
+
+    /// Whether `value` is a value of the struct of `pattern` whose fields match their patterns
+    fn matches_struct(
+        &mut self,
+        pattern: &'a StructPattern<'a>,
+        value: &Value,
+        span: Span,
+    ) -> Result<bool> {
+        let name = match (pattern.name, self.self_type) {
+            ("Self", Some(self_type)) => self_type,
+            (name, _) => name,
+        };
+        let declaration = *self
+            .structs
+            .get(name)
+            .ok_or_else(|| RuntimeError::UndefinedType(name.into()).at(span))?;
+        let names: Vec<_> = pattern.fields.iter().map(|field| field.name).collect();
+        check_fields(declaration, &names, !pattern.rest).map_err(|err| err.at(span))?;
+
+        let Value::Struct(struct_) = value else {
+            return Err(RuntimeError::UnexpectedType {
+                expected: "struct",
+                found: value.clone(),
+            }
+            .at(span));
+        };
+        if struct_.name != name {
+            return Ok(false);
+        }
+
+        for field in pattern.fields.iter() {
+            let value = struct_.field(field.name).expect("fields are checked above");
+            if !self.matches(&field.pattern, value, span)? {
+                return Ok(false);
+            }
+        }
 
-        Value::Void
+        Ok(true)
     }
 
-    fn eval_comparison_oper(&mut self, comp: &ComparisonOperation) -> Value {
+    /// Replaces the value of declared variables, which must keep their types
+    ///
+    /// The value is evaluated before any variable changes, so `(a, b) = (b, a)`
+    /// swaps them. A compound assignment such as `x *= y` assigns the result of
+    /// `x * y`, so `area *= 2` is allowed for a float `area`, while `count *= 2.0`
+    /// is rejected for an int `count` since the result would be a float. The
+    /// exception is `+=` on lists, which adds items to the list in place.
+    fn eval_reassignment(&mut self, reassign: &'a Reassignment<'a>, span: Span) -> Result<Value> {
+        let value = self.eval_expr(&reassign.expression)?;
+
+        // `list += value` pushes the value, or every item of it when it's a list
+        if let (Some(ArithmeticOperator::Add), Assignee::Variable(_) | Assignee::Field { .. }) =
+            (&reassign.op, &reassign.target)
+        {
+            let is_list = matches!(self.eval_assignee(&reassign.target), Ok(Value::List(_)));
+            if is_list {
+                let place = place_of(&reassign.target);
+                self.change(&place, |place| {
+                    if let Value::List(items) = place {
+                        let items = Rc::make_mut(items);
+                        match value {
+                            Value::List(other) => items.extend(other.iter().cloned()),
+                            value => items.push(value),
+                        }
+                    }
+                    Ok(())
+                })
+                .map_err(|err| err.at(span))?;
+                return Ok(Value::Void);
+            }
+        }
+
+        let value = match reassign.op {
+            Some(ref op) => {
+                let current = self
+                    .eval_assignee(&reassign.target)
+                    .map_err(|err| err.at(span))?;
+                eval_arithmetic(op, current, value, self.limits).map_err(|err| err.at(span))?
+            }
+            None => value,
+        };
+
+        self.assign(&reassign.target, value)
+            .map_err(|err| err.at(span))?;
+
+        Ok(Value::Void)
+    }
+
+    /// The current value of `target`, with the values of a tuple of variables in a tuple
+    fn eval_assignee(&self, target: &Assignee) -> std::result::Result<Value, RuntimeError> {
+        match target {
+            Assignee::Variable(name) => self.eval_identifier(name),
+            Assignee::Field { base, field } => field_of(&self.eval_assignee(base)?, field).cloned(),
+            Assignee::Tuple(targets) => {
+                let items = targets
+                    .iter()
+                    .map(|target| self.eval_assignee(target))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(Value::Tuple(items.into()))
+            }
+        }
+    }
+
+    fn assign(&mut self, target: &Assignee, value: Value) -> std::result::Result<(), RuntimeError> {
+        if let Assignee::Tuple(targets) = target {
+            for (target, value) in targets.iter().zip(untuple(value, targets.len())?) {
+                self.assign(target, value)?;
+            }
+            return Ok(());
+        }
+
+        self.change(&place_of(target), |place| {
+            if !same_type(place, &value) {
+                return Err(RuntimeError::ReassignmentTypeMismatch {
+                    name: target.to_string(),
+                    expected: place.type_name(),
+                    found: value,
+                });
+            }
+
+            *place = value;
+            Ok(())
+        })
+    }
+
+    /// Changes the value stored at `place` in place with `change`
+    fn change<T>(
+        &self,
+        place: &Place,
+        change: impl FnOnce(&mut Value) -> std::result::Result<T, RuntimeError>,
+    ) -> std::result::Result<T, RuntimeError> {
+        let name = place.variable();
+        let mut value = self
+            .symbol_table
+            .get_value_mut(name)
+            .ok_or_else(|| RuntimeError::UndefinedVariable(name.to_string()))?;
+
+        change(locate(&mut value, place)?)
+    }
+
+    fn eval_comparison_oper(
+        &mut self,
+        comp: &'a ComparisonOperation<'a>,
+        span: Span,
+    ) -> Result<Value> {
         use crate::ast::ComparisonOperator::*;
 
-        let left = self.eval_expr(&comp.left);
-        let right = self.eval_expr(&comp.right);
+        let left = self.eval_expr(&comp.left)?;
+        let right = self.eval_expr(&comp.right)?;
 
-        match comp.op {
+        let result = match comp.op {
             Equals => eval_equals(left, right),
             NotEquals => eval_not_equals(left, right),
             Less => eval_less(left, right),
             LessEquals => eval_less_equals(left, right),
             Greater => eval_greater(left, right),
             GreaterEquals => eval_greater_equals(left, right),
-        }
-    }
+        };
 
-    fn eval_arithmetic_oper(&mut self, arith: &ArithmeticOperation) -> Value {
-        use crate::ast::ArithmeticOperator::*;
+        result.map_err(|err| err.at(span))
+    }
 
-        let left = self.eval_expr(&arith.left);
-        let right = self.eval_expr(&arith.right);
+    fn eval_arithmetic_oper(
+        &mut self,
+        arith: &'a ArithmeticOperation<'a>,
+        span: Span,
+    ) -> Result<Value> {
+        let left = self.eval_expr(&arith.left)?;
+        let right = self.eval_expr(&arith.right)?;
 
-        match arith.op {
-            Add => eval_add(left, right),
-            Subtract => eval_subtract(left, right),
-            Multiply => eval_multiply(left, right),
-            Divide => eval_divide(left, right),
-            Power => eval_power(left, right),
-            Modulo => eval_modulo(left, right),
-        }
+        eval_arithmetic(&arith.op, left, right, self.limits).map_err(|err| err.at(span))
     }
 
-    fn eval_boolean_oper(&mut self, boolean: &BooleanOperation) -> Value {
+    /// `and` and `or`, as well as `nand` and `nor`, don't evaluate their right operand
+    /// when the left one already decides the result, e.g. in `false and f()`
+    fn eval_boolean_oper(
+        &mut self,
+        boolean: &'a BooleanOperation<'a>,
+        span: Span,
+    ) -> Result<Value> {
         use crate::ast::BooleanOperator::*;
 
-        let left = self.eval_expr(&boolean.left);
-        let right = self.eval_expr(&boolean.right);
+        let left = self.eval_expr(&boolean.left)?;
+        match (&boolean.op, &left) {
+            (And, Value::Boolean(false)) | (Or, Value::Boolean(true)) => return Ok(left),
+            (Nand, Value::Boolean(false)) => return Ok(Value::Boolean(true)),
+            (Nor, Value::Boolean(true)) => return Ok(Value::Boolean(false)),
+            _ => {}
+        }
+        let right = self.eval_expr(&boolean.right)?;
 
-        match boolean.op {
+        let result = match boolean.op {
             Or => eval_or(left, right),
             And => eval_and(left, right),
+            Xor => eval_xor(left, right),
+            Nand => eval_nand(left, right),
+            Nor => eval_nor(left, right),
+        };
+
+        result.map_err(|err| err.at(span))
+    }
+
+    /// Unlike those of [`Self::eval_boolean_oper`], both operands are always evaluated
+    fn eval_bitwise_oper(
+        &mut self,
+        bitwise: &'a BitwiseOperation<'a>,
+        span: Span,
+    ) -> Result<Value> {
+        use crate::ast::BitwiseOperator::*;
+
+        let left = self.eval_expr(&bitwise.left)?;
+        let right = self.eval_expr(&bitwise.right)?;
+
+        let result = match bitwise.op {
+            BitAnd => eval_bit_and(left, right),
+            BitOr => eval_bit_or(left, right),
+            BitXor => eval_bit_xor(left, right),
+            ShiftLeft => eval_shift("<<", left, right, i64::checked_shl),
+            ShiftRight => eval_shift(">>", left, right, i64::checked_shr),
+        };
+
+        result.map_err(|err| err.at(span))
+    }
+
+    /// Calls the function that `call` names, which is the first of: a variable that holds
+    /// a function, a function of the program, a variant of an enum and a builtin function
+    fn eval_function_call(&mut self, call: &'a FunctionCall<'a>, span: Span) -> Result<Value> {
+        if let Some(Value::Function(closure)) = self.symbol_table.get_value(call.name) {
+            return self.eval_closure_call(&closure, call, span);
+        }
+        if let Some(function) = self.functions.get(call.name).copied() {
+            return self.eval_user_function_call(function, None, &call.params, span);
+        }
+        if let Some((ty, variant)) = self.enums.resolve(call.name).map_err(|err| err.at(span))? {
+            let payload = call.params.iter().collect::<Vec<_>>();
+            return self.eval_variant(ty, variant, &payload, span);
+        }
+
+        match Builtin::get(call.name) {
+            Some(builtin) => self.eval_builtin_call(builtin, call, span),
+            None => Err(RuntimeError::UndefinedFunction(call.name.into()).at(span)),
         }
     }
 
-    fn eval_function_call(&mut self, call: &FunctionCall) -> Value {
-        if call.name == "println" && call.params.len() == 1 {
-            self.eval_println(call)
-        } else if call.name == "assert" && call.params.len() == 1 {
-            self.eval_assert(call)
-        } else {
-            panic!(
-                "Function {} could not be found or has invalid number of args",
-                call.name
-            )
+    fn eval_builtin_call(
+        &mut self,
+        builtin: Builtin,
+        call: &'a FunctionCall<'a>,
+        span: Span,
+    ) -> Result<Value> {
+        if call.params.len() != builtin.arity() {
+            return Err(RuntimeError::ArityMismatch {
+                function: call.name.into(),
+                expected: builtin.arity(),
+                found: call.params.len(),
+            }
+            .at(span));
+        }
+
+        match builtin {
+            Builtin::List => Ok(Value::List(Rc::default())),
+            Builtin::Println => self.eval_println(call, span),
+            Builtin::Assert => self.eval_assert(call, span),
+            Builtin::Panic => Err(RuntimeError::Panicked.at(span)),
+            Builtin::Convert(conversion) => {
+                let value = self.eval_expr(&call.params[0])?;
+                conversion.apply(value).map_err(|err| err.at(span))
+            }
         }
     }
 
-    fn eval_assert(&mut self, call: &FunctionCall) -> Value {
-        match self.eval_expr(&call.params[0]) {
-            Value::Boolean(assert_ok) => {
-                if !assert_ok {
-                    // TODO panic only the runtime
-                    // TODO show expression that failed
-                    panic!("Assert failed");
-                } else {
-                    Value::Void
-                }
+    /// Calls a method of the receiver, which is a method of its type if it has one
+    ///
+    /// Methods may change `self`, and when they're called on a variable or on a field or
+    /// item of one, e.g. `joao.vaccinate()` or `people[0].vaccinate()`, the final value
+    /// of `self` is written back to it.
+    /// To change a copy instead, a method can assign `self` to another variable.
+    fn eval_method_call(&mut self, call: &'a MethodCall<'a>, span: Span) -> Result<Value> {
+        let (receiver, place) = self.eval_receiver(&call.receiver)?;
+        let arguments = call
+            .params
+            .iter()
+            .map(|arg| self.eval_expr(arg))
+            .collect::<Result<Vec<_>>>()?;
+
+        let ty = match receiver {
+            Value::Struct(ref struct_) => Some(struct_.name.as_str()),
+            Value::Enum(ref enum_) => Some(enum_.ty.as_str()),
+            _ => None,
+        };
+        let Some((ty, method)) = ty.and_then(|ty| self.method_of(ty, call.name)) else {
+            return eval_method(receiver, call.name, arguments).map_err(|err| err.at(span));
+        };
+        if !method.receiver {
+            return Err(RuntimeError::ReceiverMismatch {
+                function: format!("{}::{}", ty, method.name),
+                takes_self: false,
             }
-            _ => Value::Void,
+            .at(span));
+        }
+
+        if arguments.len() != method.params.len() {
+            return Err(RuntimeError::ArityMismatch {
+                function: method.name.into(),
+                expected: method.params.len(),
+                found: arguments.len(),
+            }
+            .at(span));
+        }
+
+        let (result, receiver) =
+            self.call_function(method, Some(ty), Some(receiver), arguments, span)?;
+
+        if let (Some(place), Some(receiver)) = (place, receiver) {
+            self.change(&place, |place| {
+                *place = receiver;
+                Ok(())
+            })
+            .map_err(|err| err.at(span))?;
         }
+
+        Ok(result)
     }
 
-    fn eval_println(&mut self, call: &FunctionCall) -> Value {
-        match self.eval_expr(&call.params[0]) {
-            Value::String(ref str) => {
-                let _bytes_written = self.stdout.write(str.as_bytes()).unwrap();
-                let _bytes_written = self.stdout.write(b"\n").unwrap();
+    /// Evaluates the receiver of a method call, along with where it's stored if it's stored
+    /// anywhere. The positions of items are evaluated once, so `items[next()].method()`
+    /// writes back to the item that it read.
+    fn eval_receiver(&mut self, expr: &'a Expression<'a>) -> Result<(Value, Option<Place<'a>>)> {
+        match &expr.kind {
+            ExpressionKind::Identifier(name) => match self.symbol_table.get_value(name) {
+                Some(value) => Ok((value, Some(Place::Variable(name)))),
+                // Variants without a value, like `None`, are read like variables but aren't stored anywhere
+                None => Ok((self.eval_expr(expr)?, None)),
+            },
+            ExpressionKind::FieldAccess(access) => {
+                let (base, place) = self.eval_receiver(&access.base)?;
+                let value = field_of(&base, access.field).map_err(|err| err.at(expr.span))?;
+                let place = place.map(|place| Place::Field(Box::new(place), access.field));
+                Ok((value.clone(), place))
             }
-            Value::Integer(val) => {
-                let str = format!("{}\n", val);
-                let _bytes_written = self.stdout.write(str.as_bytes()).unwrap();
+            ExpressionKind::Index(index) => {
+                let (indexed, place) = self.eval_receiver(&index.indexed)?;
+                let position = self.eval_expr(&index.index)?;
+                let place = match (place, &position) {
+                    (Some(place), Value::Integer(position)) => {
+                        Some(Place::Item(Box::new(place), *position))
+                    }
+                    _ => None,
+                };
+                let value = eval_index(indexed, position).map_err(|err| err.at(expr.span))?;
+                Ok((value, place))
             }
-            Value::Float(val) => {
-                let fmt = format!("{:.5}\n", val);
-                let _bytes_written = self.stdout.write(fmt.as_bytes()).unwrap();
+            _ => Ok((self.eval_expr(expr)?, None)),
+        }
+    }
+
+    /// Calls a function that doesn't take `self`, which belongs to `self_type` if it's
+    /// declared in an `impl`
+    fn eval_user_function_call(
+        &mut self,
+        function: &'a Function<'a>,
+        self_type: Option<&'a str>,
+        params: &'a [Expression<'a>],
+        span: Span,
+    ) -> Result<Value> {
+        if params.len() != function.params.len() {
+            return Err(RuntimeError::ArityMismatch {
+                function: function.name.into(),
+                expected: function.params.len(),
+                found: params.len(),
             }
-            Value::Boolean(val) => {
-                let fmt = format!("{}\n", val);
-                let _bytes_written = self.stdout.write(fmt.as_bytes()).unwrap();
+            .at(span));
+        }
+
+        // Arguments are evaluated in the caller's scope
+        let arguments = params
+            .iter()
+            .map(|arg| self.eval_expr(arg))
+            .collect::<Result<Vec<_>>>()?;
+
+        let (result, _) = self.call_function(function, self_type, None, arguments, span)?;
+        Ok(result)
+    }
+
+    /// Runs the body of `function` in a new frame, with its parameters bound to `arguments`
+    ///
+    /// Functions of a type get the type that `Self` refers to, and methods also get
+    /// the value of `self`, whose final value is returned along with the result.
+    fn call_function(
+        &mut self,
+        function: &'a Function<'a>,
+        self_type: Option<&'a str>,
+        receiver: Option<Value>,
+        arguments: Vec<Value>,
+        span: Span,
+    ) -> Result<(Value, Option<Value>)> {
+        if self.calls.len() == self.limits.max_call_depth {
+            return Err(RuntimeError::StackOverflow(Some(function.name.into())).at(span));
+        }
+
+        self.symbol_table.push_frame();
+        let takes_self = receiver.is_some();
+        if let Some(receiver) = receiver {
+            self.symbol_table.create_var("self", receiver);
+        }
+        for (param, value) in function.params.iter().zip(arguments) {
+            self.symbol_table.create_var(param.name, value);
+        }
+
+        let outer_self_type = std::mem::replace(&mut self.self_type, self_type);
+        self.calls.push(function.name);
+        let result = self.eval_expr(&function.body);
+        self.calls.pop();
+        self.self_type = outer_self_type;
+
+        let receiver = match takes_self {
+            true => self
+                .symbol_table
+                .get_value_mut("self")
+                .map(|mut value| std::mem::replace(&mut *value, Value::Void)),
+            false => None,
+        };
+        self.symbol_table.pop_frame();
+
+        result.map(|result| (result, receiver))
+    }
+
+    fn eval_closure_call(
+        &mut self,
+        closure: &Rc<Closure>,
+        call: &'a FunctionCall<'a>,
+        span: Span,
+    ) -> Result<Value> {
+        let arguments = call
+            .params
+            .iter()
+            .map(|arg| self.eval_expr(arg))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.call_closure(closure, call.name, arguments, span)
+    }
+
+    /// Runs the body of the lambda of `closure` in a closed frame, with the variables it
+    /// captured, itself under the name it was declared as and its parameters bound to `arguments`
+    fn call_closure(
+        &mut self,
+        closure: &Rc<Closure>,
+        name: &'a str,
+        arguments: Vec<Value>,
+        span: Span,
+    ) -> Result<Value> {
+        let lambda = self.lambdas[closure.lambda];
+        if arguments.len() != lambda.params.len() {
+            return Err(RuntimeError::ArityMismatch {
+                function: name.into(),
+                expected: lambda.params.len(),
+                found: arguments.len(),
             }
-            Value::Char(val) => {
-                let fmt = format!("{}\n", val);
-                let _bytes_written = self.stdout.write(fmt.as_bytes()).unwrap();
+            .at(span));
+        }
+        if self.calls.len() == self.limits.max_call_depth {
+            return Err(RuntimeError::StackOverflow(Some(name.into())).at(span));
+        }
+
+        self.symbol_table.push_closed_frame();
+        for (name, variable) in closure.captures.iter() {
+            self.symbol_table.share_var(name, Rc::clone(variable));
+        }
+        if let Some(name) = &closure.name {
+            let value = Value::Function(Rc::clone(closure));
+            self.symbol_table.create_var(name, value);
+        }
+        for (param, value) in lambda.params.iter().zip(arguments) {
+            self.symbol_table.create_var(param, value);
+        }
+
+        self.calls.push(name);
+        let result = self.eval_expr(&lambda.body);
+        self.calls.pop();
+        self.symbol_table.pop_frame();
+
+        result
+    }
+
+    /// Makes a function out of `lambda`, capturing the variables it uses, global ones
+    /// included, which it shares with the code around it. A lambda declared as `name` calls
+    /// itself by it
+    fn eval_lambda(&mut self, lambda: &'a Lambda<'a>, name: Option<&str>) -> Value {
+        let position = match self
+            .lambdas
+            .iter()
+            .position(|known| std::ptr::eq(*known, lambda))
+        {
+            Some(position) => position,
+            None => {
+                self.lambdas.push(lambda);
+                self.lambdas.len() - 1
             }
-            _ => todo!(),
+        };
+
+        let mut names = Vec::new();
+        used_names(&lambda.body, &mut names);
+
+        let mut captures: Vec<(String, Variable)> = Vec::new();
+        for used in names {
+            if lambda.params.contains(&used)
+                || name == Some(used)
+                || captures.iter().any(|(other, _)| other == used)
+            {
+                continue;
+            }
+            if let Some(variable) = self.symbol_table.get_var(used) {
+                captures.push((used.into(), Rc::clone(variable)));
+            }
+        }
+
+        Value::Function(Rc::new(Closure {
+            lambda: position,
+            captures,
+            name: name.map(Into::into),
+        }))
+    }
+
+    fn eval_assert(&mut self, call: &'a FunctionCall<'a>, span: Span) -> Result<Value> {
+        let condition = &call.params[0];
+        match self.eval_expr(condition)? {
+            Value::Boolean(true) => Ok(Value::Void),
+            Value::Boolean(false) => Err(RuntimeError::AssertionFailed.at(span)),
+            found => Err(RuntimeError::UnexpectedType {
+                expected: "bool",
+                found,
+            }
+            .at(condition.span)),
         }
-        Value::Void
     }
 
-    fn eval_identifier(&self, name: &str) -> Value {
+    fn eval_println(&mut self, call: &'a FunctionCall<'a>, span: Span) -> Result<Value> {
+        let value = self.eval_expr(&call.params[0])?;
+        writeln!(self.stdout, "{}", value).map_err(|err| RuntimeError::from(err).at(span))?;
+
+        Ok(Value::Void)
+    }
+
+    fn eval_identifier(&self, name: &str) -> std::result::Result<Value, RuntimeError> {
         if let Some(value) = self.symbol_table.get_value(name) {
-            value.clone()
-        } else {
-            // TODO panic inside runtime
-            panic!("Could not find {}", name)
+            return Ok(value);
+        }
+
+        // A variant without a value, like `None`, unless a variable shadows it
+        match self.enums.resolve(name)? {
+            Some((ty, variant)) if variant.payload.is_none() => {
+                Ok(Value::Enum(Rc::new(EnumValue {
+                    ty: ty.into(),
+                    variant: name.into(),
+                    payload: None,
+                })))
+            }
+            Some(_) => Err(RuntimeError::PayloadMismatch {
+                variant: name.into(),
+                expected: true,
+            }),
+            None => Err(RuntimeError::UndefinedVariable(name.into())),
         }
     }
 }
 
-fn eval_or(left: Value, right: Value) -> Value {
+/// Roughly where the end of the native stack is, as the address of a local of this call
+fn stack_position() -> usize {
+    let marker = 0u8;
+    std::ptr::addr_of!(marker) as usize
+}
+
+fn eval_or(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
+    use crate::mem::Value::Boolean;
+    match (&left, &right) {
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(*val1 || *val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "or",
+            left,
+            right,
+        }),
+    }
+}
+
+fn eval_and(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::Boolean;
     match (&left, &right) {
-        (Boolean(val1), Boolean(val2)) => Boolean(*val1 || *val2),
-        _ => todo!(
-            "Boolean OR not implemented for {:?} and {:?}",
-            &left,
-            &right
-        ),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(*val1 && *val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "and",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_and(left: Value, right: Value) -> Value {
+fn eval_xor(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::Boolean;
     match (&left, &right) {
-        (Boolean(val1), Boolean(val2)) => Boolean(*val1 && *val2),
-        _ => todo!(
-            "Boolean AND not implemented for {:?} and {:?}",
-            &left,
-            &right
-        ),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 != val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "xor",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_not(value: Value) -> Value {
+fn eval_nand(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
+    use crate::mem::Value::Boolean;
+    match (&left, &right) {
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(!(*val1 && *val2))),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "nand",
+            left,
+            right,
+        }),
+    }
+}
+
+fn eval_nor(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
+    use crate::mem::Value::Boolean;
+    match (&left, &right) {
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(!(*val1 || *val2))),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "nor",
+            left,
+            right,
+        }),
+    }
+}
+
+fn eval_bit_and(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
+    use crate::mem::Value::{Boolean, Integer};
+    match (&left, &right) {
+        (Integer(val1), Integer(val2)) => Ok(Integer(val1 & val2)),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 & val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "&",
+            left,
+            right,
+        }),
+    }
+}
+
+fn eval_bit_or(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
+    use crate::mem::Value::{Boolean, Integer};
+    match (&left, &right) {
+        (Integer(val1), Integer(val2)) => Ok(Integer(val1 | val2)),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 | val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "|",
+            left,
+            right,
+        }),
+    }
+}
+
+fn eval_bit_xor(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
+    use crate::mem::Value::{Boolean, Integer};
+    match (&left, &right) {
+        (Integer(val1), Integer(val2)) => Ok(Integer(val1 ^ val2)),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 ^ val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "~",
+            left,
+            right,
+        }),
+    }
+}
+
+/// Shifts the bits of an int by 0 to 63 places, the only amounts that keep any of them
+fn eval_shift(
+    op: &'static str,
+    left: Value,
+    right: Value,
+    shift: fn(i64, u32) -> Option<i64>,
+) -> std::result::Result<Value, RuntimeError> {
+    use crate::mem::Value::Integer;
+    match (&left, &right) {
+        (Integer(val1), Integer(val2)) => u32::try_from(*val2)
+            .ok()
+            .and_then(|places| shift(*val1, places))
+            .map(Integer)
+            .ok_or(RuntimeError::ShiftOutOfRange { op, left, right }),
+        _ => Err(RuntimeError::TypeMismatch { op, left, right }),
+    }
+}
+
+fn eval_not(value: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::Boolean;
     match &value {
-        Boolean(value) => Boolean(!value),
-        _ => todo!("Boolean NOT not implemented for {:?}", &value),
+        Boolean(value) => Ok(Boolean(!value)),
+        _ => Err(RuntimeError::InvalidOperand { op: "not", value }),
     }
 }
 
-fn eval_minus(value: Value) -> Value {
+fn eval_bit_not(value: Value) -> std::result::Result<Value, RuntimeError> {
+    use crate::mem::Value::Integer;
+    match &value {
+        Integer(value) => Ok(Integer(!value)),
+        _ => Err(RuntimeError::InvalidOperand { op: "~", value }),
+    }
+}
+
+fn eval_minus(value: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
     match &value {
-        Integer(val) => Integer(-val),
-        Float(val) => Float(-val),
-        _ => todo!("Unary minus not implemented for {:?}", &value),
+        Integer(val) => val
+            .checked_neg()
+            .map(Integer)
+            .ok_or(RuntimeError::IntegerOverflow {
+                op: "-",
+                left: Integer(0),
+                right: value,
+            }),
+        Float(val) => Ok(Float(-val)),
+        _ => Err(RuntimeError::InvalidOperand { op: "-", value }),
+    }
+}
+
+fn eval_arithmetic(
+    op: &ArithmeticOperator,
+    left: Value,
+    right: Value,
+    limits: Limits,
+) -> std::result::Result<Value, RuntimeError> {
+    use crate::ast::ArithmeticOperator::*;
+    match op {
+        Add => eval_add(left, right),
+        Subtract => eval_subtract(left, right),
+        Multiply => eval_multiply(left, right, limits.max_repeat_len),
+        Divide => eval_divide(left, right),
+        Power => eval_power(left, right),
+        Modulo => eval_modulo(left, right),
+    }
+}
+
+/// Checks that `fields` are declared by the struct and given once, and that no field
+/// is left out when `complete`
+fn check_fields(
+    declaration: &Struct,
+    fields: &[&str],
+    complete: bool,
+) -> std::result::Result<(), RuntimeError> {
+    for (idx, field) in fields.iter().enumerate() {
+        if !declaration.fields.iter().any(|decl| decl.name == *field) {
+            return Err(RuntimeError::UndefinedField {
+                struct_name: declaration.name.into(),
+                field: field.to_string(),
+            });
+        }
+        if fields[..idx].contains(field) {
+            return Err(RuntimeError::FieldRepeated(field.to_string()));
+        }
+    }
+
+    if complete {
+        if let Some(decl) = declaration
+            .fields
+            .iter()
+            .find(|decl| !fields.contains(&decl.name))
+        {
+            return Err(RuntimeError::MissingField {
+                struct_name: declaration.name.into(),
+                field: decl.name.into(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the names of the variables and functions that `expr` uses
+fn used_names<'e>(expr: &'e Expression, names: &mut Vec<&'e str>) {
+    use ExpressionKind::*;
+    match &expr.kind {
+        Identifier(name) => names.push(name),
+        BooleanLiteral(_) | IntegerLiteral(_) | FloatLiteral(_) | CharLiteral(_)
+        | StringLiteral(_) => {}
+        Assignment(assign) => used_names(&assign.expression, names),
+        Reassignment(reassign) => {
+            assigned_names(&reassign.target, names);
+            used_names(&reassign.expression, names);
+        }
+        ListLiteral(items) | TupleLiteral(items) => {
+            items.iter().for_each(|item| used_names(item, names))
+        }
+        StructLiteral(literal) => literal
+            .fields
+            .iter()
+            .for_each(|field| used_names(&field.expression, names)),
+        VariantLiteral(literal) => literal
+            .params
+            .iter()
+            .for_each(|param| used_names(param, names)),
+        Index(index) => {
+            used_names(&index.indexed, names);
+            used_names(&index.index, names);
+        }
+        FieldAccess(access) => used_names(&access.base, names),
+        FunctionCall(call) => {
+            names.push(call.name);
+            call.params
+                .iter()
+                .for_each(|param| used_names(param, names));
+        }
+        MethodCall(call) => {
+            used_names(&call.receiver, names);
+            call.params
+                .iter()
+                .for_each(|param| used_names(param, names));
+        }
+        ArithOperation(arith) => {
+            used_names(&arith.left, names);
+            used_names(&arith.right, names);
+        }
+        CompOperation(comp) => {
+            used_names(&comp.left, names);
+            used_names(&comp.right, names);
+        }
+        BoolOperation(boolean) => {
+            used_names(&boolean.left, names);
+            used_names(&boolean.right, names);
+        }
+        BitOperation(bitwise) => {
+            used_names(&bitwise.left, names);
+            used_names(&bitwise.right, names);
+        }
+        Not(expr) | Minus(expr) | BitNot(expr) => used_names(expr, names),
+        If(if_) => {
+            used_names(&if_.condition, names);
+            used_names(&if_.then, names);
+            for elif in if_.elifs.iter() {
+                used_names(&elif.condition, names);
+                used_names(&elif.then, names);
+            }
+            if let Some(ref otherwise) = if_.otherwise {
+                used_names(otherwise, names);
+            }
+        }
+        Match(match_) => {
+            used_names(&match_.scrutinee, names);
+            for arm in match_.arms.iter() {
+                if let Some(ref guard) = arm.guard {
+                    used_names(guard, names);
+                }
+                used_names(&arm.body, names);
+            }
+        }
+        // The variables of a nested lambda are captured along the way
+        Lambda(lambda) => used_names(&lambda.body, names),
+        Block(block) => {
+            block
+                .statements
+                .iter()
+                .for_each(|statement| used_names(statement, names));
+            if let Some(ref tail) = block.tail {
+                used_names(tail, names);
+            }
+        }
+    }
+}
+
+/// Collects the names of the variables that `target` changes
+fn assigned_names<'e>(target: &'e Assignee, names: &mut Vec<&'e str>) {
+    match target {
+        Assignee::Variable(name) => names.push(name),
+        Assignee::Field { base, .. } => assigned_names(base, names),
+        Assignee::Tuple(targets) => targets
+            .iter()
+            .for_each(|target| assigned_names(target, names)),
+    }
+}
+
+/// Whether `value` can replace `current`, having the same type and being a value of the
+/// same struct or enum for those
+fn same_type(current: &Value, value: &Value) -> bool {
+    match (current, value) {
+        (Value::Struct(current), Value::Struct(value)) => current.name == value.name,
+        (Value::Enum(current), Value::Enum(value)) => current.ty == value.ty,
+        _ => std::mem::discriminant(current) == std::mem::discriminant(value),
+    }
+}
+
+/// The value of a field of a struct
+fn field_of<'v>(value: &'v Value, field: &str) -> std::result::Result<&'v Value, RuntimeError> {
+    match value {
+        Value::Struct(struct_) => {
+            struct_
+                .field(field)
+                .ok_or_else(|| RuntimeError::UndefinedField {
+                    struct_name: struct_.name.clone(),
+                    field: field.into(),
+                })
+        }
+        found => Err(RuntimeError::UnexpectedType {
+            expected: "struct",
+            found: found.clone(),
+        }),
+    }
+}
+
+/// The assignee `target` as a place, which it is unless it's a tuple of them
+fn place_of<'e>(target: &Assignee<'e>) -> Place<'e> {
+    match target {
+        Assignee::Variable(name) => Place::Variable(name),
+        Assignee::Field { base, field } => Place::Field(Box::new(place_of(base)), field),
+        Assignee::Tuple(_) => unreachable!("tuples are assigned item by item"),
+    }
+}
+
+impl Place<'_> {
+    /// The variable that the place is in
+    fn variable(&self) -> &str {
+        match self {
+            Place::Variable(name) => name,
+            Place::Field(base, _) | Place::Item(base, _) => base.variable(),
+        }
+    }
+}
+
+/// Where `place` is stored within `value`, the value of the variable it's in
+fn locate<'v>(
+    value: &'v mut Value,
+    place: &Place,
+) -> std::result::Result<&'v mut Value, RuntimeError> {
+    match place {
+        Place::Variable(_) => Ok(value),
+        Place::Field(base, field) => field_mut(locate(value, base)?, field),
+        // Copies of the list or tuple keep the items they had
+        Place::Item(base, index) => match locate(value, base)? {
+            Value::List(items) => item_at_mut(Rc::make_mut(items).as_mut_slice(), *index),
+            Value::Tuple(items) => item_at_mut(Rc::make_mut(items), *index),
+            found => Err(RuntimeError::UnexpectedType {
+                expected: "list or tuple",
+                found: found.clone(),
+            }),
+        },
+    }
+}
+
+/// Where a field of a struct is stored, to be changed in place
+fn field_mut<'v>(
+    value: &'v mut Value,
+    field: &str,
+) -> std::result::Result<&'v mut Value, RuntimeError> {
+    match value {
+        // Copies of the struct keep the fields they had
+        Value::Struct(struct_) => {
+            let struct_ = Rc::make_mut(struct_);
+            let struct_name = struct_.name.clone();
+            struct_
+                .field_mut(field)
+                .ok_or_else(|| RuntimeError::UndefinedField {
+                    struct_name,
+                    field: field.to_string(),
+                })
+        }
+        found => Err(RuntimeError::UnexpectedType {
+            expected: "struct",
+            found: found.clone(),
+        }),
+    }
+}
+
+/// Takes a tuple apart into its items, which must be `len`
+fn untuple(value: Value, len: usize) -> std::result::Result<Vec<Value>, RuntimeError> {
+    match value {
+        Value::Tuple(ref items) if items.len() == len => Ok(items.to_vec()),
+        Value::Tuple(_) => Err(RuntimeError::TupleLengthMismatch {
+            expected: len,
+            found: value,
+        }),
+        found => Err(RuntimeError::UnexpectedType {
+            expected: "tuple",
+            found,
+        }),
+    }
+}
+
+/// Calls one of the methods that values have built in
+fn eval_method(
+    receiver: Value,
+    method: &str,
+    arguments: Vec<Value>,
+) -> std::result::Result<Value, RuntimeError> {
+    match (&receiver, method) {
+        // Accessors return an `Option` instead of failing when there's no item
+        (Value::List(_) | Value::Tuple(_) | Value::String(_), "get") => {
+            let index = match <[Value; 1]>::try_from(arguments) {
+                Ok([Value::Integer(index)]) => index,
+                Ok([found]) => {
+                    return Err(RuntimeError::UnexpectedType {
+                        expected: "int",
+                        found,
+                    })
+                }
+                Err(arguments) => {
+                    return Err(RuntimeError::ArityMismatch {
+                        function: method.into(),
+                        expected: 1,
+                        found: arguments.len(),
+                    })
+                }
+            };
+            let item = match receiver {
+                Value::List(items) => item_at(&items, index).ok().cloned(),
+                Value::Tuple(items) => item_at(&items, index).ok().cloned(),
+                Value::String(string) => {
+                    let chars: Vec<char> = string.chars().collect();
+                    position(index, chars.len()).map(|position| Value::Char(chars[position]))
+                }
+                _ => unreachable!("matched above"),
+            };
+            Ok(item.map_or_else(Value::none, Value::some))
+        }
+        _ => {
+            let type_name = match receiver {
+                Value::Struct(struct_) => struct_.name.clone(),
+                Value::Enum(enum_) => enum_.ty.clone(),
+                _ => receiver.type_name().into(),
+            };
+            Err(RuntimeError::UndefinedMethod {
+                type_name,
+                method: method.into(),
+            })
+        }
     }
 }
 
-fn eval_add(left: Value, right: Value) -> Value {
+/// The item of a list or tuple at a position, where negative positions count from the end
+fn eval_index(indexed: Value, index: Value) -> std::result::Result<Value, RuntimeError> {
+    let index = match index {
+        Value::Integer(index) => index,
+        found => {
+            return Err(RuntimeError::UnexpectedType {
+                expected: "int",
+                found,
+            })
+        }
+    };
+
+    match indexed {
+        Value::List(items) => item_at(&items, index).cloned(),
+        Value::Tuple(items) => item_at(&items, index).cloned(),
+        found => Err(RuntimeError::UnexpectedType {
+            expected: "list or tuple",
+            found,
+        }),
+    }
+}
+
+fn item_at(items: &[Value], index: i64) -> std::result::Result<&Value, RuntimeError> {
+    position(index, items.len())
+        .map(|position| &items[position])
+        .ok_or(RuntimeError::IndexOutOfBounds {
+            index,
+            len: items.len(),
+        })
+}
+
+fn item_at_mut(items: &mut [Value], index: i64) -> std::result::Result<&mut Value, RuntimeError> {
+    let len = items.len();
+    position(index, len)
+        .map(|position| &mut items[position])
+        .ok_or(RuntimeError::IndexOutOfBounds { index, len })
+}
+
+/// Where `index` points to in a sequence of `len` items, if it's within its bounds
+fn position(index: i64, len: usize) -> Option<usize> {
+    let position = if index < 0 {
+        usize::try_from(index.unsigned_abs())
+            .ok()
+            .and_then(|from_end| len.checked_sub(from_end))
+    } else {
+        usize::try_from(index).ok()
+    };
+
+    position.filter(|&position| position < len)
+}
+
+/// Applies a checked integer operation, reporting an overflow if it fails
+fn checked(
+    op: &'static str,
+    val1: i64,
+    val2: i64,
+    f: impl FnOnce(i64, i64) -> Option<i64>,
+) -> std::result::Result<Value, RuntimeError> {
+    f(val1, val2)
+        .map(Value::Integer)
+        .ok_or(RuntimeError::IntegerOverflow {
+            op,
+            left: Value::Integer(val1),
+            right: Value::Integer(val2),
+        })
+}
+
+fn eval_add(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Integer(val1 + val2),
-        (Float(val1), Float(val2)) => Float(val1 + val2),
-        (Integer(val1), Float(val2)) => Float(*val1 as f64 + val2),
-        (Float(val1), Integer(val2)) => Float(val1 + *val2 as f64),
-        (String(val1), String(val2)) => String(val1.to_owned() + val2),
-        _ => todo!("Addition of {:?} and {:?} not implemented", &left, &right),
+        (Integer(val1), Integer(val2)) => checked("+", *val1, *val2, i64::checked_add),
+        (Float(val1), Float(val2)) => Ok(Float(val1 + val2)),
+        (Integer(val1), Float(val2)) => Ok(Float(*val1 as f64 + val2)),
+        (Float(val1), Integer(val2)) => Ok(Float(val1 + *val2 as f64)),
+        (String(val1), String(val2)) => Ok(String(val1.to_owned() + val2)),
+        (List(val1), List(val2)) => Ok(List(Rc::new([&val1[..], &val2[..]].concat()))),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "+",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_subtract(left: Value, right: Value) -> Value {
+fn eval_subtract(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Integer(val1 - val2),
-        (Float(val1), Float(val2)) => Float(val1 - val2),
-        (Integer(val1), Float(val2)) => Float(*val1 as f64 - val2),
-        (Float(val1), Integer(val2)) => Float(val1 - *val2 as f64),
-        _ => todo!(
-            "Subtraction of {:?} and {:?} not implemented",
-            &left,
-            &right
-        ),
+        (Integer(val1), Integer(val2)) => checked("-", *val1, *val2, i64::checked_sub),
+        (Float(val1), Float(val2)) => Ok(Float(val1 - val2)),
+        (Integer(val1), Float(val2)) => Ok(Float(*val1 as f64 - val2)),
+        (Float(val1), Integer(val2)) => Ok(Float(val1 - *val2 as f64)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "-",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_multiply(left: Value, right: Value) -> Value {
+/// Multiplies numbers, or repeats a string or list, e.g. `"ab" * 3` or `3 * [1, 2]`, up to
+/// a length of `max_len`
+fn eval_multiply(
+    left: Value,
+    right: Value,
+    max_len: usize,
+) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Integer(val1 * val2),
-        (Float(val1), Float(val2)) => Float(val1 * val2),
-        (Integer(val1), Float(val2)) => Float(*val1 as f64 * val2),
-        (Float(val1), Integer(val2)) => Float(val1 * *val2 as f64),
-        _ => todo!(
-            "Multiplication of {:?} and {:?} not implemented",
-            &left,
-            &right
-        ),
+        (Integer(val1), Integer(val2)) => checked("*", *val1, *val2, i64::checked_mul),
+        (Float(val1), Float(val2)) => Ok(Float(val1 * val2)),
+        (Integer(val1), Float(val2)) => Ok(Float(*val1 as f64 * val2)),
+        (Float(val1), Integer(val2)) => Ok(Float(val1 * *val2 as f64)),
+        (String(string), Integer(count)) | (Integer(count), String(string)) => {
+            let count = repetitions(string.len(), *count, max_len)?;
+            Ok(String(string.repeat(count)))
+        }
+        (List(items), Integer(count)) | (Integer(count), List(items)) => {
+            let count = repetitions(items.len(), *count, max_len)?;
+            let repeated = (0..count).flat_map(|_| items.iter().cloned()).collect();
+            Ok(List(Rc::new(repeated)))
+        }
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "*",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_divide(left: Value, right: Value) -> Value {
+/// How many times a string or list of `len` is repeated by `* count`, which may not be
+/// negative nor make it longer than `max_len`
+fn repetitions(len: usize, count: i64, max_len: usize) -> std::result::Result<usize, RuntimeError> {
+    let Ok(times) = usize::try_from(count) else {
+        return Err(RuntimeError::NegativeRepetition(count));
+    };
+    if len == 0 {
+        return Ok(0);
+    }
+
+    match len.checked_mul(times) {
+        Some(total) if total <= max_len => Ok(times),
+        _ => Err(RuntimeError::RepetitionTooLong {
+            len,
+            count,
+            max: max_len,
+        }),
+    }
+}
+
+fn eval_divide(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
-    // TODO check division by zero
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Integer(val1 / val2),
-        (Float(val1), Float(val2)) => Float(val1 / val2),
-        (Integer(val1), Float(val2)) => Float(*val1 as f64 / val2),
-        (Float(val1), Integer(val2)) => Float(val1 / *val2 as f64),
-        _ => todo!("Divisio of {:?} and {:?} not implemented", &left, &right),
+        (Integer(_), Integer(0)) => Err(RuntimeError::DivisionByZero { op: "/", left }),
+        (Integer(val1), Integer(val2)) => checked("/", *val1, *val2, i64::checked_div),
+        (Float(val1), Float(val2)) => Ok(Float(val1 / val2)),
+        (Integer(val1), Float(val2)) => Ok(Float(*val1 as f64 / val2)),
+        (Float(val1), Integer(val2)) => Ok(Float(val1 / *val2 as f64)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "/",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_power(left: Value, right: Value) -> Value {
+fn eval_power(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
     match (&left, &right) {
-        // TODO int < 0
-        // TODO int > u32
-        (Integer(val1), Integer(val2)) => Integer(val1.pow(*val2 as u32)),
-        (Float(val1), Float(val2)) => Float(val1.powf(*val2)),
-        (Integer(val1), Float(val2)) => Float((*val1 as f64).powf(*val2)),
-        (Float(val1), Integer(val2)) => Float(val1.powf(*val2 as f64)),
-        _ => todo!(
-            "Exponentiation of {:?} by {:?} not implemented",
-            &left,
-            &right
-        ),
+        // Negative exponents can't produce an integer
+        (Integer(val1), Integer(val2)) if *val2 < 0 => Ok(Float((*val1 as f64).powf(*val2 as f64))),
+        (Integer(val1), Integer(val2)) => checked("^", *val1, *val2, |base, exp| {
+            u32::try_from(exp)
+                .ok()
+                .and_then(|exp| base.checked_pow(exp))
+        }),
+        (Float(val1), Float(val2)) => Ok(Float(val1.powf(*val2))),
+        (Integer(val1), Float(val2)) => Ok(Float((*val1 as f64).powf(*val2))),
+        (Float(val1), Integer(val2)) => Ok(Float(val1.powf(*val2 as f64))),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "^",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_modulo(left: Value, right: Value) -> Value {
+fn eval_modulo(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
-    // TODO div by zero
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Integer(val1 % val2),
-        (Float(val1), Float(val2)) => Float(val1 % val2),
-        (Integer(val1), Float(val2)) => Float(*val1 as f64 % val2),
-        (Float(val1), Integer(val2)) => Float(val1 % *val2 as f64),
-        _ => todo!("Modulo of {:?} by {:?} not implemented", &left, &right),
+        (Integer(_), Integer(0)) => Err(RuntimeError::DivisionByZero { op: "%", left }),
+        (Integer(val1), Integer(val2)) => checked("%", *val1, *val2, i64::checked_rem),
+        (Float(val1), Float(val2)) => Ok(Float(val1 % val2)),
+        (Integer(val1), Float(val2)) => Ok(Float(*val1 as f64 % val2)),
+        (Float(val1), Integer(val2)) => Ok(Float(val1 % *val2 as f64)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "%",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_equals(left: Value, right: Value) -> Value {
+fn eval_equals(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
+    use std::cmp::Ordering::*;
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Boolean(val1 == val2),
-        (String(val1), String(val2)) => Boolean(val1 == val2),
-        (Char(val1), Char(val2)) => Boolean(val1 == val2),
+        (Integer(val1), Integer(val2)) => Ok(Boolean(val1 == val2)),
+        (String(val1), String(val2)) => Ok(Boolean(val1 == val2)),
+        (Char(val1), Char(val2)) => Ok(Boolean(val1 == val2)),
         (Float(val1), Float(val2)) =>
         {
             #[allow(clippy::float_cmp)]
-            Boolean(val1 == val2)
+            Ok(Boolean(val1 == val2))
         }
-        (Boolean(val1), Boolean(val2)) => Boolean(val1 == val2),
-        _ => todo!("Comparison of {:?} and {:?} not implemented", &left, &right),
+        (Integer(int), Float(float)) | (Float(float), Integer(int)) => {
+            Ok(Boolean(compare_int_float(*int, *float) == Some(Equal)))
+        }
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 == val2)),
+        (List(val1), List(val2)) => Ok(Boolean(val1 == val2)),
+        (Tuple(val1), Tuple(val2)) => Ok(Boolean(val1 == val2)),
+        (Enum(val1), Enum(val2)) => Ok(Boolean(val1 == val2)),
+        (Struct(val1), Struct(val2)) => Ok(Boolean(val1 == val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "==",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_less(left: Value, right: Value) -> Value {
+fn eval_less(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
+    use std::cmp::Ordering::*;
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Boolean(val1 < val2),
-        (String(val1), String(val2)) => Boolean(val1 < val2),
-        (Char(val1), Char(val2)) => Boolean(val1 < val2),
-        (Float(val1), Float(val2)) => Boolean(val1 < val2),
-        (Boolean(val1), Boolean(val2)) => Boolean(val1 < val2),
-        _ => todo!("Comparison of {:?} and {:?} not implemented", &left, &right),
+        (Integer(val1), Integer(val2)) => Ok(Boolean(val1 < val2)),
+        (String(val1), String(val2)) => Ok(Boolean(val1 < val2)),
+        (Char(val1), Char(val2)) => Ok(Boolean(val1 < val2)),
+        (Float(val1), Float(val2)) => Ok(Boolean(val1 < val2)),
+        (Integer(int), Float(float)) => Ok(Boolean(matches!(
+            compare_int_float(*int, *float),
+            Some(Less)
+        ))),
+        (Float(float), Integer(int)) => Ok(Boolean(matches!(
+            compare_int_float(*int, *float),
+            Some(Greater)
+        ))),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 < val2)),
+        (List(val1), List(val2)) => Ok(Boolean(val1 < val2)),
+        (Tuple(val1), Tuple(val2)) => Ok(Boolean(val1 < val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "<",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_less_equals(left: Value, right: Value) -> Value {
+fn eval_less_equals(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
+    use std::cmp::Ordering::*;
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Boolean(val1 <= val2),
-        (String(val1), String(val2)) => Boolean(val1 <= val2),
-        (Char(val1), Char(val2)) => Boolean(val1 <= val2),
-        (Float(val1), Float(val2)) => Boolean(val1 <= val2),
-        (Boolean(val1), Boolean(val2)) => Boolean(val1 <= val2),
-        _ => todo!("Comparison of {:?} and {:?} not implemented", &left, &right),
+        (Integer(val1), Integer(val2)) => Ok(Boolean(val1 <= val2)),
+        (String(val1), String(val2)) => Ok(Boolean(val1 <= val2)),
+        (Char(val1), Char(val2)) => Ok(Boolean(val1 <= val2)),
+        (Float(val1), Float(val2)) => Ok(Boolean(val1 <= val2)),
+        (Integer(int), Float(float)) => Ok(Boolean(matches!(
+            compare_int_float(*int, *float),
+            Some(Less | Equal)
+        ))),
+        (Float(float), Integer(int)) => Ok(Boolean(matches!(
+            compare_int_float(*int, *float),
+            Some(Greater | Equal)
+        ))),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 <= val2)),
+        (List(val1), List(val2)) => Ok(Boolean(val1 <= val2)),
+        (Tuple(val1), Tuple(val2)) => Ok(Boolean(val1 <= val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "<=",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_greater(left: Value, right: Value) -> Value {
+fn eval_greater(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
+    use std::cmp::Ordering::*;
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Boolean(val1 > val2),
-        (String(val1), String(val2)) => Boolean(val1 > val2),
-        (Char(val1), Char(val2)) => Boolean(val1 > val2),
-        (Float(val1), Float(val2)) => Boolean(val1 > val2),
-        (Boolean(val1), Boolean(val2)) => Boolean(val1 > val2),
-        _ => todo!("Comparison of {:?} and {:?} not implemented", &left, &right),
+        (Integer(val1), Integer(val2)) => Ok(Boolean(val1 > val2)),
+        (String(val1), String(val2)) => Ok(Boolean(val1 > val2)),
+        (Char(val1), Char(val2)) => Ok(Boolean(val1 > val2)),
+        (Float(val1), Float(val2)) => Ok(Boolean(val1 > val2)),
+        (Integer(int), Float(float)) => Ok(Boolean(matches!(
+            compare_int_float(*int, *float),
+            Some(Greater)
+        ))),
+        (Float(float), Integer(int)) => Ok(Boolean(matches!(
+            compare_int_float(*int, *float),
+            Some(Less)
+        ))),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 > val2)),
+        (List(val1), List(val2)) => Ok(Boolean(val1 > val2)),
+        (Tuple(val1), Tuple(val2)) => Ok(Boolean(val1 > val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: ">",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_greater_equals(left: Value, right: Value) -> Value {
+fn eval_greater_equals(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
+    use std::cmp::Ordering::*;
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Boolean(val1 >= val2),
-        (String(val1), String(val2)) => Boolean(val1 >= val2),
-        (Char(val1), Char(val2)) => Boolean(val1 >= val2),
-        (Float(val1), Float(val2)) => Boolean(val1 >= val2),
-        (Boolean(val1), Boolean(val2)) => Boolean(val1 >= val2),
-        _ => todo!("Comparison of {:?} and {:?} not implemented", &left, &right),
+        (Integer(val1), Integer(val2)) => Ok(Boolean(val1 >= val2)),
+        (String(val1), String(val2)) => Ok(Boolean(val1 >= val2)),
+        (Char(val1), Char(val2)) => Ok(Boolean(val1 >= val2)),
+        (Float(val1), Float(val2)) => Ok(Boolean(val1 >= val2)),
+        (Integer(int), Float(float)) => Ok(Boolean(matches!(
+            compare_int_float(*int, *float),
+            Some(Greater | Equal)
+        ))),
+        (Float(float), Integer(int)) => Ok(Boolean(matches!(
+            compare_int_float(*int, *float),
+            Some(Less | Equal)
+        ))),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 >= val2)),
+        (List(val1), List(val2)) => Ok(Boolean(val1 >= val2)),
+        (Tuple(val1), Tuple(val2)) => Ok(Boolean(val1 >= val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: ">=",
+            left,
+            right,
+        }),
     }
 }
 
-fn eval_not_equals(left: Value, right: Value) -> Value {
+fn eval_not_equals(left: Value, right: Value) -> std::result::Result<Value, RuntimeError> {
     use crate::mem::Value::*;
+    use std::cmp::Ordering::*;
     match (&left, &right) {
-        (Integer(val1), Integer(val2)) => Boolean(val1 != val2),
-        (String(val1), String(val2)) => Boolean(val1 != val2),
-        (Char(val1), Char(val2)) => Boolean(val1 != val2),
+        (Integer(val1), Integer(val2)) => Ok(Boolean(val1 != val2)),
+        (String(val1), String(val2)) => Ok(Boolean(val1 != val2)),
+        (Char(val1), Char(val2)) => Ok(Boolean(val1 != val2)),
         (Float(val1), Float(val2)) =>
         {
             #[allow(clippy::float_cmp)]
-            Boolean(val1 != val2)
+            Ok(Boolean(val1 != val2))
+        }
+        (Integer(int), Float(float)) | (Float(float), Integer(int)) => {
+            Ok(Boolean(compare_int_float(*int, *float) != Some(Equal)))
         }
-        (Boolean(val1), Boolean(val2)) => Boolean(val1 != val2),
-        _ => todo!("Comparison of {:?} and {:?} not implemented", &left, &right),
+        (Boolean(val1), Boolean(val2)) => Ok(Boolean(val1 != val2)),
+        (List(val1), List(val2)) => Ok(Boolean(val1 != val2)),
+        (Tuple(val1), Tuple(val2)) => Ok(Boolean(val1 != val2)),
+        (Enum(val1), Enum(val2)) => Ok(Boolean(val1 != val2)),
+        (Struct(val1), Struct(val2)) => Ok(Boolean(val1 != val2)),
+        _ => Err(RuntimeError::TypeMismatch {
+            op: "!=",
+            left,
+            right,
+        }),
     }
 }
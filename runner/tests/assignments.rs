@@ -1,24 +1,30 @@
-use runner::ast::{Assignment, CacauProgram, Expression, FunctionCall, HighLevelItem};
+use runner::ast::{Assignment, CacauProgram, ExpressionKind, FunctionCall, HighLevelItem, Pattern};
 
 #[test]
 fn simple_assign() {
     let program = CacauProgram {
         items: vec![
-            HighLevelItem::Expr(Expression::Assignment(Box::new(Assignment {
-                name: "hello",
-                expression: Expression::StringLiteral("Hello, World!"),
-                type_annotation: None,
-            }))),
-            HighLevelItem::Expr(Expression::FunctionCall(FunctionCall {
-                name: "println",
-                params: vec![Expression::Identifier("hello")],
-            })),
+            HighLevelItem::Expr(
+                ExpressionKind::Assignment(Box::new(Assignment {
+                    pattern: Pattern::Identifier("hello"),
+                    expression: ExpressionKind::StringLiteral("Hello, World!".into()).into(),
+                    type_annotation: None,
+                }))
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::FunctionCall(FunctionCall {
+                    name: "println",
+                    params: vec![ExpressionKind::Identifier("hello").into()],
+                })
+                .into(),
+            ),
         ],
     };
 
     // run
     let mut stdout = Vec::new();
-    runner::Runner::run(&program, &mut stdout);
+    runner::Runner::run(&program, &mut stdout).unwrap();
 
     // check output
     assert_eq!(String::from_utf8(stdout).unwrap(), "Hello, World!\n")
@@ -28,57 +34,87 @@ fn simple_assign() {
 fn assigns_of_various_types() {
     let program = CacauProgram {
         items: vec![
-            HighLevelItem::Expr(Expression::Assignment(Box::new(Assignment {
-                name: "text",
-                expression: Expression::StringLiteral("foo"),
-                type_annotation: None,
-            }))),
-            HighLevelItem::Expr(Expression::Assignment(Box::new(Assignment {
-                name: "integer",
-                expression: Expression::IntegerLiteral(100),
-                type_annotation: None,
-            }))),
-            HighLevelItem::Expr(Expression::Assignment(Box::new(Assignment {
-                name: "decimal",
-                expression: Expression::FloatLiteral(100.0),
-                type_annotation: None,
-            }))),
-            HighLevelItem::Expr(Expression::Assignment(Box::new(Assignment {
-                name: "character",
-                expression: Expression::CharLiteral('1'),
-                type_annotation: None,
-            }))),
-            HighLevelItem::Expr(Expression::Assignment(Box::new(Assignment {
-                name: "truth",
-                expression: Expression::BooleanLiteral(true),
-                type_annotation: None,
-            }))),
-            HighLevelItem::Expr(Expression::FunctionCall(FunctionCall {
-                name: "println",
-                params: vec![Expression::Identifier("text")],
-            })),
-            HighLevelItem::Expr(Expression::FunctionCall(FunctionCall {
-                name: "println",
-                params: vec![Expression::Identifier("integer")],
-            })),
-            HighLevelItem::Expr(Expression::FunctionCall(FunctionCall {
-                name: "println",
-                params: vec![Expression::Identifier("decimal")],
-            })),
-            HighLevelItem::Expr(Expression::FunctionCall(FunctionCall {
-                name: "println",
-                params: vec![Expression::Identifier("character")],
-            })),
-            HighLevelItem::Expr(Expression::FunctionCall(FunctionCall {
-                name: "println",
-                params: vec![Expression::Identifier("truth")],
-            })),
+            HighLevelItem::Expr(
+                ExpressionKind::Assignment(Box::new(Assignment {
+                    pattern: Pattern::Identifier("text"),
+                    expression: ExpressionKind::StringLiteral("foo".into()).into(),
+                    type_annotation: None,
+                }))
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::Assignment(Box::new(Assignment {
+                    pattern: Pattern::Identifier("integer"),
+                    expression: ExpressionKind::IntegerLiteral(100).into(),
+                    type_annotation: None,
+                }))
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::Assignment(Box::new(Assignment {
+                    pattern: Pattern::Identifier("decimal"),
+                    expression: ExpressionKind::FloatLiteral(100.0).into(),
+                    type_annotation: None,
+                }))
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::Assignment(Box::new(Assignment {
+                    pattern: Pattern::Identifier("character"),
+                    expression: ExpressionKind::CharLiteral('1').into(),
+                    type_annotation: None,
+                }))
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::Assignment(Box::new(Assignment {
+                    pattern: Pattern::Identifier("truth"),
+                    expression: ExpressionKind::BooleanLiteral(true).into(),
+                    type_annotation: None,
+                }))
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::FunctionCall(FunctionCall {
+                    name: "println",
+                    params: vec![ExpressionKind::Identifier("text").into()],
+                })
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::FunctionCall(FunctionCall {
+                    name: "println",
+                    params: vec![ExpressionKind::Identifier("integer").into()],
+                })
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::FunctionCall(FunctionCall {
+                    name: "println",
+                    params: vec![ExpressionKind::Identifier("decimal").into()],
+                })
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::FunctionCall(FunctionCall {
+                    name: "println",
+                    params: vec![ExpressionKind::Identifier("character").into()],
+                })
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::FunctionCall(FunctionCall {
+                    name: "println",
+                    params: vec![ExpressionKind::Identifier("truth").into()],
+                })
+                .into(),
+            ),
         ],
     };
 
     // run
     let mut stdout = Vec::new();
-    runner::Runner::run(&program, &mut stdout);
+    runner::Runner::run(&program, &mut stdout).unwrap();
 
     // check output
     assert_eq!(
@@ -0,0 +1,102 @@
+use runner::{
+    ast::{
+        BitwiseOperation, BitwiseOperator, BooleanOperation, BooleanOperator, Expression,
+        ExpressionKind,
+    },
+    RuntimeError,
+};
+
+mod common;
+
+use common::{bool_, call, int, println, run};
+
+fn bitwise<'a>(left: Expression<'a>, op: BitwiseOperator, right: Expression<'a>) -> Expression<'a> {
+    ExpressionKind::BitOperation(Box::new(BitwiseOperation { left, op, right })).into()
+}
+
+#[test]
+fn ints() {
+    use BitwiseOperator::*;
+
+    let output = run(vec![
+        println(bitwise(int(12), BitAnd, int(10))),
+        println(bitwise(int(12), BitOr, int(10))),
+        println(bitwise(int(1), ShiftLeft, int(63))),
+        println(bitwise(int(-16), ShiftRight, int(2))),
+        println(ExpressionKind::BitNot(Box::new(int(0))).into()),
+        println(bitwise(int(12), BitXor, int(10))),
+    ]);
+
+    assert_eq!(output.unwrap(), "8\n14\n-9223372036854775808\n-4\n-1\n6\n");
+}
+
+#[test]
+fn booleans_evaluate_both_operands() {
+    use BitwiseOperator::*;
+
+    let output = run(vec![
+        println(bitwise(bool_(true), BitAnd, bool_(false))),
+        println(bitwise(bool_(false), BitOr, bool_(true))),
+        println(bitwise(bool_(true), BitXor, bool_(true))),
+    ]);
+    assert_eq!(output.unwrap(), "false\ntrue\nfalse\n");
+
+    let output = run(vec![println(bitwise(
+        bool_(true),
+        BitOr,
+        call("panic", vec![]),
+    ))]);
+    assert!(matches!(output, Err(RuntimeError::Panicked)));
+
+    let output = run(vec![println(bitwise(
+        bool_(false),
+        BitAnd,
+        call("panic", vec![]),
+    ))]);
+    assert!(matches!(output, Err(RuntimeError::Panicked)));
+}
+
+#[test]
+fn invalid_operands() {
+    use BitwiseOperator::*;
+
+    let output = run(vec![println(bitwise(int(1), ShiftLeft, int(64)))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ShiftOutOfRange { op: "<<", .. })
+    ));
+
+    let output = run(vec![println(bitwise(int(1), ShiftRight, int(-1)))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ShiftOutOfRange { op: ">>", .. })
+    ));
+
+    let output = run(vec![println(bitwise(int(1), BitAnd, bool_(true)))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::TypeMismatch { op: "&", .. })
+    ));
+
+    // `xor` is only for booleans
+    let output = run(vec![println(
+        ExpressionKind::BoolOperation(Box::new(BooleanOperation {
+            left: int(12),
+            op: BooleanOperator::Xor,
+            right: int(10),
+        }))
+        .into(),
+    )]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::TypeMismatch { op: "xor", .. })
+    ));
+
+    let output = run(vec![println(
+        ExpressionKind::BitNot(Box::new(bool_(true))).into(),
+    )]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::InvalidOperand { op: "~", .. })
+    ));
+}
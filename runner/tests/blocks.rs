@@ -0,0 +1,98 @@
+use runner::{
+    ast::{
+        ArithmeticOperation, ArithmeticOperator, ComparisonOperation, ComparisonOperator,
+        ExpressionKind, HighLevelItem, If,
+    },
+    RuntimeError,
+};
+
+mod common;
+
+use common::{block, call, function, int, let_, println, run, var};
+
+#[test]
+fn statements_run_in_order() {
+    let output = run(vec![HighLevelItem::Expr(block(
+        vec![call("println", vec![int(1)]), call("println", vec![int(2)])],
+        None,
+    ))]);
+
+    assert_eq!(output.unwrap(), "1\n2\n");
+}
+
+#[test]
+fn tail_is_the_value_of_the_block() {
+    let output = run(vec![
+        // { let x = 2; x }
+        println(block(vec![let_("x", int(2))], Some(var("x")))),
+        // { let x = 2; x; }
+        println(block(vec![let_("x", int(2)), var("x")], None)),
+    ]);
+
+    assert_eq!(output.unwrap(), "2\n()\n");
+}
+
+#[test]
+fn blocks_have_their_own_scope() {
+    let output = run(vec![
+        HighLevelItem::Expr(let_("x", int(1))),
+        println(block(vec![let_("x", int(2))], Some(var("x")))),
+        println(var("x")),
+        HighLevelItem::Expr(block(vec![let_("y", int(3))], None)),
+        println(var("y")),
+    ]);
+
+    assert!(matches!(output, Err(RuntimeError::UndefinedVariable(name)) if name == "y"));
+}
+
+#[test]
+fn function_bodies() {
+    // fn count_down n: int { if n > 0 { println(n); count_down(n - 1); } }
+    let condition = ExpressionKind::CompOperation(Box::new(ComparisonOperation {
+        left: var("n"),
+        op: ComparisonOperator::Greater,
+        right: int(0),
+    }));
+    let n_minus_one = ExpressionKind::ArithOperation(Box::new(ArithmeticOperation {
+        left: var("n"),
+        op: ArithmeticOperator::Subtract,
+        right: int(1),
+    }));
+    let if_ = ExpressionKind::If(Box::new(If {
+        condition: condition.into(),
+        then: block(
+            vec![
+                call("println", vec![var("n")]),
+                call("count_down", vec![n_minus_one.into()]),
+            ],
+            None,
+        ),
+        elifs: vec![],
+        otherwise: None,
+    }));
+
+    let output = run(vec![
+        function("count_down", &["n"], None, block(vec![], Some(if_.into()))),
+        HighLevelItem::Expr(call("count_down", vec![int(3)])),
+    ]);
+
+    assert_eq!(output.unwrap(), "3\n2\n1\n");
+}
+
+#[test]
+fn if_without_else_as_tail_of_a_value() {
+    let output = run(vec![println(block(
+        vec![],
+        Some(
+            ExpressionKind::If(Box::new(If {
+                condition: ExpressionKind::BooleanLiteral(true).into(),
+                then: int(1),
+                elifs: vec![],
+                otherwise: None,
+            }))
+            .into(),
+        ),
+    ))]);
+
+    assert!(matches!(output, Err(RuntimeError::IfWithoutElse)));
+}
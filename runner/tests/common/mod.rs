@@ -0,0 +1,173 @@
+//! Builders for the syntax trees of test programs, and a way to run them
+//!
+//! Each test crate only uses some of these
+#![allow(dead_code)]
+
+use runner::{
+    ast::{
+        ArithmeticOperation, ArithmeticOperator, Assignee, Assignment, Block, CacauProgram,
+        ComparisonOperation, ComparisonOperator, Expression, ExpressionKind, FieldAccess, Function,
+        FunctionArgument, FunctionCall, HighLevelItem, Index, MethodCall, Pattern, Reassignment,
+        Span, Struct, StructField,
+    },
+    Limits, RuntimeError,
+};
+
+/// Runs `items` as a program, returning what it printed
+pub fn run(items: Vec<HighLevelItem>) -> Result<String, RuntimeError> {
+    run_with_limits(items, Limits::default())
+}
+
+pub fn run_with_limits(items: Vec<HighLevelItem>, limits: Limits) -> Result<String, RuntimeError> {
+    let program = CacauProgram { items };
+
+    let mut stdout = Vec::new();
+    runner::Runner::run_with_limits(&program, &mut stdout, limits).map_err(|err| err.error)?;
+
+    Ok(String::from_utf8(stdout).unwrap())
+}
+
+pub fn println(expression: Expression) -> HighLevelItem {
+    HighLevelItem::Expr(call("println", vec![expression]))
+}
+
+pub fn call<'a>(name: &'a str, params: Vec<Expression<'a>>) -> Expression<'a> {
+    ExpressionKind::FunctionCall(FunctionCall { name, params }).into()
+}
+
+pub fn var(name: &str) -> Expression<'_> {
+    ExpressionKind::Identifier(name).into()
+}
+
+pub fn int<'a>(integer: i64) -> Expression<'a> {
+    ExpressionKind::IntegerLiteral(integer).into()
+}
+
+pub fn float<'a>(float: f64) -> Expression<'a> {
+    ExpressionKind::FloatLiteral(float).into()
+}
+
+pub fn string(string: &str) -> Expression<'_> {
+    ExpressionKind::StringLiteral(string.into()).into()
+}
+
+pub fn bool_<'a>(boolean: bool) -> Expression<'a> {
+    ExpressionKind::BooleanLiteral(boolean).into()
+}
+
+pub fn list<'a>(items: Vec<Expression<'a>>) -> Expression<'a> {
+    ExpressionKind::ListLiteral(items).into()
+}
+
+pub fn tuple<'a>(items: Vec<Expression<'a>>) -> Expression<'a> {
+    ExpressionKind::TupleLiteral(items).into()
+}
+
+/// `let <name> = <expression>`
+pub fn let_<'a>(name: &'a str, expression: Expression<'a>) -> Expression<'a> {
+    ExpressionKind::Assignment(Box::new(Assignment {
+        pattern: Pattern::Identifier(name),
+        type_annotation: None,
+        expression,
+    }))
+    .into()
+}
+
+/// `<target> = <expression>`, or `<target> <op>= <expression>`
+pub fn reassign<'a>(
+    target: Assignee<'a>,
+    op: Option<ArithmeticOperator>,
+    expression: Expression<'a>,
+) -> Expression<'a> {
+    ExpressionKind::Reassignment(Box::new(Reassignment {
+        target,
+        op,
+        expression,
+    }))
+    .into()
+}
+
+pub fn arith<'a>(
+    left: Expression<'a>,
+    op: ArithmeticOperator,
+    right: Expression<'a>,
+) -> Expression<'a> {
+    ExpressionKind::ArithOperation(Box::new(ArithmeticOperation { left, op, right })).into()
+}
+
+pub fn compare<'a>(
+    left: Expression<'a>,
+    op: ComparisonOperator,
+    right: Expression<'a>,
+) -> Expression<'a> {
+    ExpressionKind::CompOperation(Box::new(ComparisonOperation { left, op, right })).into()
+}
+
+pub fn block<'a>(statements: Vec<Expression<'a>>, tail: Option<Expression<'a>>) -> Expression<'a> {
+    ExpressionKind::Block(Box::new(Block { statements, tail })).into()
+}
+
+pub fn field<'a>(base: Expression<'a>, field: &'a str) -> Expression<'a> {
+    ExpressionKind::FieldAccess(Box::new(FieldAccess { base, field })).into()
+}
+
+/// `<indexed>[<index>]`
+pub fn index<'a>(indexed: Expression<'a>, index: i64) -> Expression<'a> {
+    ExpressionKind::Index(Box::new(Index {
+        indexed,
+        index: int(index),
+    }))
+    .into()
+}
+
+/// `<receiver>.get(<index>)`
+pub fn get<'a>(receiver: Expression<'a>, index: i64) -> Expression<'a> {
+    ExpressionKind::MethodCall(Box::new(MethodCall {
+        receiver,
+        name: "get",
+        params: vec![int(index)],
+    }))
+    .into()
+}
+
+/// `fn <name> <params> -> <output> { <body> }`, where every parameter is an int
+pub fn function<'a>(
+    name: &'a str,
+    params: &[&'a str],
+    output: Option<&'a str>,
+    body: Expression<'a>,
+) -> HighLevelItem<'a> {
+    HighLevelItem::Fn(Function {
+        public: false,
+        name,
+        receiver: false,
+        params: params
+            .iter()
+            .map(|name| FunctionArgument {
+                name,
+                type_: "int",
+                span: Span::default(),
+            })
+            .collect(),
+        output,
+        body,
+        span: Span::default(),
+    })
+}
+
+/// `struct Point { x: int, y: int }`
+pub fn point_struct<'a>() -> HighLevelItem<'a> {
+    let field = |name| StructField {
+        public: false,
+        name,
+        type_: "int",
+        span: Span::default(),
+    };
+
+    HighLevelItem::Struct(Struct {
+        public: false,
+        name: "Point",
+        fields: vec![field("x"), field("y")],
+        span: Span::default(),
+    })
+}
@@ -1,8 +1,12 @@
 use runner::ast::{
-    Assignment, CacauProgram, ComparisonOperation, ComparisonOperator, Expression, FunctionCall,
-    HighLevelItem,
+    Assignment, CacauProgram, ComparisonOperation, ComparisonOperator, Expression, ExpressionKind,
+    FunctionCall, HighLevelItem, Pattern,
 };
 
+mod common;
+
+use common::{call, compare, float, int, list, println, run, string, tuple};
+
 #[test]
 fn comparisons() {
     use runner::ast::ComparisonOperator::*;
@@ -12,39 +16,145 @@ fn comparisons() {
         op: ComparisonOperator,
         value: Expression<'static>,
     ) -> HighLevelItem<'static> {
-        HighLevelItem::Expr(Expression::FunctionCall(FunctionCall {
-            name: "assert",
-            params: vec![Expression::CompOperation(Box::new(ComparisonOperation {
-                left: Expression::Identifier(var),
-                op,
-                right: value,
-            }))],
-        }))
+        HighLevelItem::Expr(
+            ExpressionKind::FunctionCall(FunctionCall {
+                name: "assert",
+                params: vec![ExpressionKind::CompOperation(Box::new(ComparisonOperation {
+                    left: ExpressionKind::Identifier(var).into(),
+                    op,
+                    right: value,
+                }))
+                .into()],
+            })
+            .into(),
+        )
     }
 
     let program = CacauProgram {
         items: vec![
-            HighLevelItem::Expr(Expression::FunctionCall(FunctionCall {
-                name: "assert",
-                params: vec![Expression::BooleanLiteral(true)],
-            })),
-            HighLevelItem::Expr(Expression::Assignment(Box::new(Assignment {
-                name: "text",
-                type_annotation: None,
-                expression: Expression::StringLiteral("foo"),
-            }))),
-            assert_cmp("text", Equals, Expression::StringLiteral("foo")),
-            assert_cmp("text", GreaterEquals, Expression::StringLiteral("foo")),
-            assert_cmp("text", LessEquals, Expression::StringLiteral("foo")),
-            assert_cmp("text", Greater, Expression::StringLiteral("aaa")),
-            assert_cmp("text", GreaterEquals, Expression::StringLiteral("aaa")),
-            assert_cmp("text", Less, Expression::StringLiteral("zzz")),
-            assert_cmp("text", LessEquals, Expression::StringLiteral("zzz")),
-            assert_cmp("text", NotEquals, Expression::StringLiteral("bar")),
+            HighLevelItem::Expr(
+                ExpressionKind::FunctionCall(FunctionCall {
+                    name: "assert",
+                    params: vec![ExpressionKind::BooleanLiteral(true).into()],
+                })
+                .into(),
+            ),
+            HighLevelItem::Expr(
+                ExpressionKind::Assignment(Box::new(Assignment {
+                    pattern: Pattern::Identifier("text"),
+                    type_annotation: None,
+                    expression: ExpressionKind::StringLiteral("foo".into()).into(),
+                }))
+                .into(),
+            ),
+            assert_cmp(
+                "text",
+                Equals,
+                ExpressionKind::StringLiteral("foo".into()).into(),
+            ),
+            assert_cmp(
+                "text",
+                GreaterEquals,
+                ExpressionKind::StringLiteral("foo".into()).into(),
+            ),
+            assert_cmp(
+                "text",
+                LessEquals,
+                ExpressionKind::StringLiteral("foo".into()).into(),
+            ),
+            assert_cmp(
+                "text",
+                Greater,
+                ExpressionKind::StringLiteral("aaa".into()).into(),
+            ),
+            assert_cmp(
+                "text",
+                GreaterEquals,
+                ExpressionKind::StringLiteral("aaa".into()).into(),
+            ),
+            assert_cmp(
+                "text",
+                Less,
+                ExpressionKind::StringLiteral("zzz".into()).into(),
+            ),
+            assert_cmp(
+                "text",
+                LessEquals,
+                ExpressionKind::StringLiteral("zzz".into()).into(),
+            ),
+            assert_cmp(
+                "text",
+                NotEquals,
+                ExpressionKind::StringLiteral("bar".into()).into(),
+            ),
         ],
     };
 
     // run
     let mut stdout = Vec::new();
-    runner::Runner::run(&program, &mut stdout);
+    runner::Runner::run(&program, &mut stdout).unwrap();
+}
+
+#[test]
+fn ints_and_floats() {
+    use runner::ast::ComparisonOperator::*;
+
+    let println_cmp = |left, op, right| println(compare(left, op, right));
+    let nan = || call("float", vec![string("NaN")]);
+
+    let output = run(vec![
+        println_cmp(float(0.0), Equals, int(0)),
+        println_cmp(int(2), NotEquals, float(2.5)),
+        println_cmp(int(3), GreaterEquals, float(2.5)),
+        println_cmp(float(2.5), Less, int(3)),
+        println_cmp(int(-2), Greater, float(-2.5)),
+        // 2^53 + 1 has no exact float, and would round to 2^53
+        println_cmp(int(9007199254740993), Equals, float(9007199254740992.0)),
+        println_cmp(int(9007199254740993), Greater, float(9007199254740992.0)),
+        println_cmp(int(i64::MAX), Less, float(9223372036854775807.0)),
+        println_cmp(int(i64::MIN), Equals, float(-9223372036854775808.0)),
+        // NaN is neither equal, less nor greater than any number
+        println_cmp(nan(), Equals, int(1)),
+        println_cmp(nan(), NotEquals, int(1)),
+        println_cmp(int(1), LessEquals, nan()),
+        println_cmp(int(1), GreaterEquals, nan()),
+    ]);
+
+    assert_eq!(
+        output.unwrap(),
+        "true\ntrue\ntrue\ntrue\ntrue\nfalse\ntrue\ntrue\ntrue\nfalse\ntrue\nfalse\nfalse\n"
+    );
+}
+
+#[test]
+fn ints_and_floats_in_containers() {
+    use runner::ast::ComparisonOperator::*;
+
+    let println_cmp = |left, op, right| println(compare(left, op, right));
+
+    let output = run(vec![
+        println_cmp(list(vec![int(1)]), Equals, list(vec![float(1.0)])),
+        println_cmp(
+            call("Some", vec![int(1)]),
+            Equals,
+            call("Some", vec![float(1.0)]),
+        ),
+        println_cmp(
+            tuple(vec![int(1), int(2)]),
+            LessEquals,
+            tuple(vec![int(1), float(2.5)]),
+        ),
+        println_cmp(
+            list(vec![float(0.5), int(3)]),
+            Greater,
+            list(vec![int(0), int(4)]),
+        ),
+        println_cmp(
+            list(vec![int(9007199254740993)]),
+            Equals,
+            list(vec![float(9007199254740992.0)]),
+        ),
+    ]);
+
+    assert_eq!(output.unwrap(), "true\ntrue\ntrue\ntrue\nfalse\n");
 }
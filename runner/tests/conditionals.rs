@@ -0,0 +1,123 @@
+use runner::{
+    ast::{
+        ComparisonOperation, ComparisonOperator, Elif, Expression, ExpressionKind, Function,
+        HighLevelItem, If, Span,
+    },
+    RuntimeError,
+};
+
+mod common;
+
+use common::{call, int, let_, run, string, var};
+
+fn less<'a>(left: Expression<'a>, right: Expression<'a>) -> Expression<'a> {
+    ExpressionKind::CompOperation(Box::new(ComparisonOperation {
+        left,
+        op: ComparisonOperator::Less,
+        right,
+    }))
+    .into()
+}
+
+fn equals<'a>(left: Expression<'a>, right: Expression<'a>) -> Expression<'a> {
+    ExpressionKind::CompOperation(Box::new(ComparisonOperation {
+        left,
+        op: ComparisonOperator::Equals,
+        right,
+    }))
+    .into()
+}
+
+/// if number < 0 { "Negative" } elif number == 0 { "Zero" } else { "Positive" }
+fn sign(number: i64) -> Expression<'static> {
+    ExpressionKind::If(Box::new(If {
+        condition: less(int(number), int(0)),
+        then: string("Negative"),
+        elifs: vec![Elif {
+            condition: equals(int(number), int(0)),
+            then: string("Zero"),
+        }],
+        otherwise: Some(string("Positive")),
+    }))
+    .into()
+}
+
+#[test]
+fn if_elif_else_as_value() {
+    let output = run(vec![
+        HighLevelItem::Expr(let_("text", sign(5))),
+        HighLevelItem::Expr(call("println", vec![var("text")])),
+        HighLevelItem::Expr(call("println", vec![sign(0)])),
+        HighLevelItem::Expr(call("println", vec![sign(-5)])),
+    ]);
+
+    assert_eq!(output.unwrap(), "Positive\nZero\nNegative\n");
+}
+
+#[test]
+fn if_without_else_as_statement() {
+    let output = run(vec![HighLevelItem::Expr(
+        ExpressionKind::If(Box::new(If {
+            condition: ExpressionKind::BooleanLiteral(false).into(),
+            then: call("println", vec![int(1)]),
+            elifs: vec![],
+            otherwise: None,
+        }))
+        .into(),
+    )]);
+
+    assert_eq!(output.unwrap(), "");
+}
+
+#[test]
+fn if_without_else_as_value() {
+    let if_without_else = || -> Expression<'static> {
+        ExpressionKind::If(Box::new(If {
+            condition: ExpressionKind::BooleanLiteral(true).into(),
+            then: string("alo"),
+            elifs: vec![],
+            otherwise: None,
+        }))
+        .into()
+    };
+
+    // Reported before anything runs
+    let output = run(vec![
+        HighLevelItem::Expr(call("println", vec![int(1)])),
+        HighLevelItem::Expr(let_("text", if_without_else())),
+    ]);
+    assert!(matches!(output, Err(RuntimeError::IfWithoutElse)));
+
+    // Functions with an output type return the value of their body
+    let output = run(vec![HighLevelItem::Fn(Function {
+        public: false,
+        name: "maybe",
+        receiver: false,
+        params: vec![],
+        output: Some("string"),
+        body: if_without_else(),
+        span: Span::default(),
+    })]);
+    assert!(matches!(output, Err(RuntimeError::IfWithoutElse)));
+}
+
+#[test]
+fn condition_must_be_a_boolean() {
+    let output = run(vec![HighLevelItem::Expr(
+        ExpressionKind::If(Box::new(If {
+            condition: int(1),
+            then: int(2),
+            elifs: vec![],
+            otherwise: None,
+        }))
+        .into(),
+    )]);
+
+    assert!(matches!(
+        output,
+        Err(RuntimeError::UnexpectedType {
+            expected: "bool",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,124 @@
+use runner::{
+    ast::{
+        ArithmeticOperation, ArithmeticOperator, Expression, ExpressionKind, Function,
+        HighLevelItem, Span,
+    },
+    mem::Value,
+    RuntimeError,
+};
+
+mod common;
+
+use common::{call, float, int, println, run, string};
+
+/// Prints `<function>(<value>)`
+fn convert<'a>(function: &'a str, value: Expression<'a>) -> HighLevelItem<'a> {
+    println(call(function, vec![value]))
+}
+
+#[test]
+fn to_string() {
+    let sum = ExpressionKind::ArithOperation(Box::new(ArithmeticOperation {
+        left: int(2),
+        op: ArithmeticOperator::Add,
+        right: float(2.0),
+    }));
+
+    let output = run(vec![
+        convert("string", sum.into()),
+        convert("string", int(100)),
+        convert("string", ExpressionKind::CharLiteral('1').into()),
+        convert("string", ExpressionKind::BooleanLiteral(false).into()),
+        convert(
+            "string",
+            ExpressionKind::ListLiteral(vec![string("a")]).into(),
+        ),
+    ]);
+
+    assert_eq!(output.unwrap(), "4.00000\n100\n1\nfalse\n[\"a\"]\n");
+}
+
+#[test]
+fn to_numbers() {
+    let output = run(vec![
+        convert("int", float(55.1)),
+        convert("int", float(-0.5)),
+        convert("int", string("-10")),
+        convert("int", ExpressionKind::BooleanLiteral(true).into()),
+        convert("float", int(0)),
+        convert("float", string("8.5")),
+    ]);
+
+    assert_eq!(output.unwrap(), "55\n0\n-10\n1\n0.00000\n8.50000\n");
+}
+
+#[test]
+fn to_bool() {
+    let output = run(vec![
+        convert("bool", int(0)),
+        convert("bool", int(12345678)),
+        convert("bool", string("false")),
+    ]);
+
+    assert_eq!(output.unwrap(), "false\ntrue\nfalse\n");
+}
+
+#[test]
+fn invalid_conversions() {
+    let output = run(vec![convert("int", string("ten"))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::InvalidConversion { to: "int", value: Value::String(ref string) })
+            if string == "ten"
+    ));
+
+    let output = run(vec![convert("float", string("8,5"))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::InvalidConversion { to: "float", .. })
+    ));
+
+    // Floats out of the range of an int aren't clamped to it
+    let output = run(vec![convert("int", float(1e19))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::InvalidConversion {
+            to: "int",
+            value: Value::Float(_)
+        })
+    ));
+
+    let output = run(vec![convert("bool", string("yes"))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::InvalidConversion { to: "bool", .. })
+    ));
+}
+
+#[test]
+fn functions_shadow_builtins() {
+    let output = run(vec![
+        HighLevelItem::Fn(Function {
+            public: false,
+            name: "string",
+            receiver: false,
+            params: vec![],
+            output: Some("string"),
+            body: string("mine"),
+            span: Span::default(),
+        }),
+        println(call("string", vec![])),
+        convert("int", int(1)),
+    ]);
+    assert_eq!(output.unwrap(), "mine\n1\n");
+
+    let output = run(vec![println(call("int", vec![int(1), int(2)]))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ArityMismatch {
+            ref function,
+            expected: 1,
+            found: 2,
+        }) if function == "int"
+    ));
+}
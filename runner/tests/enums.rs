@@ -0,0 +1,201 @@
+use runner::{
+    ast::{
+        Assignee, ComparisonOperator, Enum, Expression, ExpressionKind, HighLevelItem, Span,
+        Variant, VariantLiteral,
+    },
+    mem::Value,
+    RuntimeError,
+};
+
+mod common;
+
+use common::{call, compare, int, let_, println, reassign, run, string, var};
+
+/// `enum <name> { <variant>(<payload>), ... }`
+fn enum_<'a>(name: &'a str, variants: Vec<(&'a str, Option<&'a str>)>) -> HighLevelItem<'a> {
+    HighLevelItem::Enum(Enum {
+        public: false,
+        name,
+        variants: variants
+            .into_iter()
+            .map(|(name, payload)| Variant {
+                name,
+                payload,
+                span: Span::default(),
+            })
+            .collect(),
+        span: Span::default(),
+    })
+}
+
+/// `enum User { Admin(string), Guest }`
+fn user_enum<'a>() -> HighLevelItem<'a> {
+    enum_("User", vec![("Admin", Some("string")), ("Guest", None)])
+}
+
+fn variant<'a>(ty: &'a str, variant: &'a str, payload: Option<Expression<'a>>) -> Expression<'a> {
+    ExpressionKind::VariantLiteral(Box::new(VariantLiteral {
+        ty,
+        name: variant,
+        params: payload.into_iter().collect(),
+    }))
+    .into()
+}
+
+#[test]
+fn constructors_and_printing() {
+    let output = run(vec![
+        user_enum(),
+        println(variant("User", "Admin", Some(string("joao")))),
+        println(variant("User", "Guest", None)),
+        // The type can be left out
+        println(call("Admin", vec![string("maria")])),
+        println(var("Guest")),
+    ]);
+
+    assert_eq!(
+        output.unwrap(),
+        "Admin(\"joao\")\nGuest\nAdmin(\"maria\")\nGuest\n"
+    );
+}
+
+#[test]
+fn equality() {
+    use ComparisonOperator::*;
+
+    let output = run(vec![
+        user_enum(),
+        println(compare(
+            variant("User", "Admin", Some(string("joao"))),
+            Equals,
+            call("Admin", vec![string("joao")]),
+        )),
+        println(compare(
+            variant("User", "Admin", Some(string("joao"))),
+            Equals,
+            variant("User", "Admin", Some(string("maria"))),
+        )),
+        println(compare(var("Guest"), NotEquals, var("Guest"))),
+        println(compare(
+            var("Guest"),
+            NotEquals,
+            call("Admin", vec![string("joao")]),
+        )),
+    ]);
+
+    assert_eq!(output.unwrap(), "true\nfalse\nfalse\ntrue\n");
+}
+
+#[test]
+fn variables_keep_their_enum() {
+    let output = run(vec![
+        user_enum(),
+        enum_("Status", vec![("Ready", None)]),
+        HighLevelItem::Expr(let_("user", var("Guest"))),
+        HighLevelItem::Expr(reassign(
+            Assignee::Variable("user"),
+            None,
+            call("Admin", vec![string("joao")]),
+        )),
+        println(var("user")),
+    ]);
+    assert_eq!(output.unwrap(), "Admin(\"joao\")\n");
+
+    let output = run(vec![
+        user_enum(),
+        enum_("Status", vec![("Ready", None)]),
+        HighLevelItem::Expr(let_("user", var("Guest"))),
+        HighLevelItem::Expr(reassign(Assignee::Variable("user"), None, var("Ready"))),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ReassignmentTypeMismatch {
+            expected: "enum",
+            found: Value::Enum(_),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn payloads_are_checked_against_the_declaration() {
+    let output = run(vec![user_enum(), println(var("Admin"))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::PayloadMismatch { ref variant, expected: true }) if variant == "Admin"
+    ));
+
+    let output = run(vec![
+        user_enum(),
+        println(call("Admin", vec![string("a"), string("b")])),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::PayloadMismatch { expected: true, .. })
+    ));
+
+    let output = run(vec![
+        user_enum(),
+        println(variant("User", "Guest", Some(int(1)))),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::PayloadMismatch { ref variant, expected: false }) if variant == "Guest"
+    ));
+}
+
+#[test]
+fn undefined_variants() {
+    let output = run(vec![user_enum(), println(variant("User", "Owner", None))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::UndefinedVariant { ref enum_name, ref variant })
+            if enum_name == "User" && variant == "Owner"
+    ));
+
+    let output = run(vec![println(variant("Role", "Owner", None))]);
+    assert!(matches!(output, Err(RuntimeError::UndefinedType(name)) if name == "Role"));
+
+    let output = run(vec![user_enum(), user_enum()]);
+    assert!(matches!(output, Err(RuntimeError::TypeRedefined(name)) if name == "User"));
+
+    // `Option` is part of the prelude
+    let output = run(vec![enum_("Option", vec![])]);
+    assert!(matches!(output, Err(RuntimeError::TypeRedefined(name)) if name == "Option"));
+}
+
+#[test]
+fn variants_without_their_type_must_be_unambiguous() {
+    let output = run(vec![
+        user_enum(),
+        enum_("Visitor", vec![("Guest", None)]),
+        println(var("Guest")),
+    ]);
+    assert!(matches!(output, Err(RuntimeError::AmbiguousVariant(name)) if name == "Guest"));
+
+    let output = run(vec![
+        user_enum(),
+        enum_("Visitor", vec![("Guest", None)]),
+        println(variant("Visitor", "Guest", None)),
+    ]);
+    assert_eq!(output.unwrap(), "Guest\n");
+
+    // But the variants of the program shadow those of the prelude
+    let output = run(vec![
+        enum_(
+            "MaybeString",
+            vec![("Some", Some("string")), ("None", None)],
+        ),
+        println(compare(
+            call("Some", vec![string("a")]),
+            ComparisonOperator::Equals,
+            variant("MaybeString", "Some", Some(string("a"))),
+        )),
+        println(compare(
+            var("None"),
+            ComparisonOperator::Equals,
+            variant("Option", "None", None),
+        )),
+    ]);
+    assert_eq!(output.unwrap(), "true\nfalse\n");
+}
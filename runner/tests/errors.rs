@@ -0,0 +1,152 @@
+use runner::{
+    ast::{
+        ArithmeticOperation, ArithmeticOperator, CacauProgram, Expression, ExpressionKind,
+        HighLevelItem, Span,
+    },
+    mem::Value,
+    RuntimeError,
+};
+
+mod common;
+
+use common::{arith, call, run};
+
+#[test]
+fn failed_assertion() {
+    let result = run(vec![
+        HighLevelItem::Expr(call(
+            "println",
+            vec![ExpressionKind::IntegerLiteral(1).into()],
+        )),
+        HighLevelItem::Expr(call(
+            "assert",
+            vec![ExpressionKind::BooleanLiteral(false).into()],
+        )),
+        HighLevelItem::Expr(call(
+            "println",
+            vec![ExpressionKind::IntegerLiteral(2).into()],
+        )),
+    ]);
+
+    assert!(matches!(result, Err(RuntimeError::AssertionFailed)));
+}
+
+#[test]
+fn type_mismatch() {
+    let result = run(vec![HighLevelItem::Expr(call(
+        "println",
+        vec![arith(
+            ExpressionKind::IntegerLiteral(1).into(),
+            ArithmeticOperator::Add,
+            ExpressionKind::StringLiteral("one".into()).into(),
+        )],
+    ))]);
+
+    let err = result.unwrap_err();
+    assert!(matches!(
+        err,
+        RuntimeError::TypeMismatch {
+            op: "+",
+            left: Value::Integer(1),
+            right: Value::String(ref string),
+        } if string == "one"
+    ));
+    assert_eq!(
+        err.to_string(),
+        "operator `+` cannot be applied to 1 (int) and \"one\" (string)"
+    );
+}
+
+#[test]
+fn undefined_names() {
+    let result = run(vec![HighLevelItem::Expr(call(
+        "println",
+        vec![ExpressionKind::Identifier("nope").into()],
+    ))]);
+    assert!(matches!(result, Err(RuntimeError::UndefinedVariable(name)) if name == "nope"));
+
+    let result = run(vec![HighLevelItem::Expr(call("nope", vec![]))]);
+    assert!(matches!(result, Err(RuntimeError::UndefinedFunction(name)) if name == "nope"));
+}
+
+#[test]
+fn arity_mismatch() {
+    let result = run(vec![HighLevelItem::Expr(call("println", vec![]))]);
+
+    assert!(matches!(
+        result,
+        Err(RuntimeError::ArityMismatch {
+            expected: 1,
+            found: 0,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn division_by_zero() {
+    for op in [ArithmeticOperator::Divide, ArithmeticOperator::Modulo] {
+        let result = run(vec![HighLevelItem::Expr(call(
+            "println",
+            vec![arith(
+                ExpressionKind::IntegerLiteral(10).into(),
+                op,
+                ExpressionKind::IntegerLiteral(0).into(),
+            )],
+        ))]);
+
+        assert!(matches!(result, Err(RuntimeError::DivisionByZero { .. })));
+    }
+}
+
+#[test]
+fn integer_overflow() {
+    let result = run(vec![HighLevelItem::Expr(call(
+        "println",
+        vec![arith(
+            ExpressionKind::IntegerLiteral(i64::MAX).into(),
+            ArithmeticOperator::Add,
+            ExpressionKind::IntegerLiteral(1).into(),
+        )],
+    ))]);
+
+    assert!(matches!(
+        result,
+        Err(RuntimeError::IntegerOverflow { op: "+", .. })
+    ));
+}
+
+#[test]
+fn errors_point_at_the_failing_expression() {
+    let division = Expression::new(
+        ExpressionKind::ArithOperation(Box::new(ArithmeticOperation {
+            left: Expression::new(ExpressionKind::IntegerLiteral(1), Span::new(8, 9)),
+            op: ArithmeticOperator::Divide,
+            right: Expression::new(ExpressionKind::IntegerLiteral(0), Span::new(12, 13)),
+        })),
+        Span::new(8, 13),
+    );
+    let program = CacauProgram {
+        items: vec![HighLevelItem::Expr(call("println", vec![division]))],
+    };
+
+    let mut stdout = Vec::new();
+    let err = runner::Runner::run(&program, &mut stdout).unwrap_err();
+    assert_eq!(err.span, Span::new(8, 13));
+}
+
+#[test]
+fn report() {
+    let source = "let x = 1;\nassert(x == 2);\n";
+    let err = RuntimeError::AssertionFailed.at(Span::new(11, 25));
+
+    assert_eq!(
+        err.report("test.cau", source),
+        "error: assertion failed
+ --> test.cau:2:1
+  |
+2 | assert(x == 2);
+  | ^^^^^^^^^^^^^^
+"
+    );
+}
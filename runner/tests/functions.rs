@@ -0,0 +1,152 @@
+use runner::{
+    ast::{
+        ArithmeticOperator, ComparisonOperation, ComparisonOperator, ExpressionKind, HighLevelItem,
+        If,
+    },
+    Limits, RuntimeError,
+};
+
+mod common;
+
+use common::{arith, block, call, function, int, println, run, run_with_limits, var};
+
+/// fn factorial n: int -> int {
+///     if n <= 1 { 1 } else { factorial(n - 1) * n }
+/// }
+fn factorial<'a>() -> HighLevelItem<'a> {
+    let body = ExpressionKind::If(Box::new(If {
+        condition: ExpressionKind::CompOperation(Box::new(ComparisonOperation {
+            left: var("n"),
+            op: ComparisonOperator::LessEquals,
+            right: int(1),
+        }))
+        .into(),
+        then: int(1),
+        elifs: vec![],
+        otherwise: Some(arith(
+            call(
+                "factorial",
+                vec![arith(var("n"), ArithmeticOperator::Subtract, int(1))],
+            ),
+            ArithmeticOperator::Multiply,
+            var("n"),
+        )),
+    }));
+
+    function("factorial", &["n"], Some("int"), body.into())
+}
+
+#[test]
+fn call_user_function() {
+    let output = run(vec![
+        println(call("double", vec![int(21)])),
+        function(
+            "double",
+            &["x"],
+            Some("int"),
+            arith(var("x"), ArithmeticOperator::Multiply, int(2)),
+        ),
+    ]);
+
+    assert_eq!(output.unwrap(), "42\n");
+}
+
+#[test]
+fn recursion() {
+    let output = run(vec![
+        factorial(),
+        println(call("factorial", vec![int(0)])),
+        println(call("factorial", vec![int(5)])),
+        println(call("factorial", vec![int(10)])),
+    ]);
+
+    assert_eq!(output.unwrap(), "1\n120\n3628800\n");
+}
+
+#[test]
+fn arguments_are_bound_in_a_fresh_scope() {
+    let output = run(vec![
+        function("first", &["a", "b"], Some("int"), var("a")),
+        println(call("first", vec![int(1), int(2)])),
+        // The arguments of the call are not visible after it returns
+        println(var("a")),
+    ]);
+
+    assert!(matches!(output, Err(RuntimeError::UndefinedVariable(name)) if name == "a"));
+}
+
+#[test]
+fn arity_mismatch() {
+    let output = run(vec![factorial(), println(call("factorial", vec![]))]);
+
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ArityMismatch {
+            expected: 1,
+            found: 0,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn unbounded_recursion() {
+    // A test thread has less stack than the default, so the limit must know how much
+    let limits = Limits {
+        max_call_depth: usize::MAX,
+        stack_size: 1 << 20,
+        ..Limits::default()
+    };
+    let output = run_with_limits(
+        vec![
+            function(
+                "forever",
+                &["n"],
+                Some("int"),
+                call("forever", vec![var("n")]),
+            ),
+            println(call("forever", vec![int(0)])),
+        ],
+        limits,
+    );
+
+    assert!(matches!(output, Err(RuntimeError::StackOverflow(Some(name))) if name == "forever"));
+}
+
+#[test]
+fn call_depth_limit() {
+    let limits = |max_call_depth| Limits {
+        max_call_depth,
+        ..Limits::default()
+    };
+    let program = || vec![factorial(), println(call("factorial", vec![int(20)]))];
+
+    let output = run_with_limits(program(), limits(20));
+    assert_eq!(output.unwrap(), "2432902008176640000\n");
+
+    let output = run_with_limits(program(), limits(19));
+    assert!(matches!(output, Err(RuntimeError::StackOverflow(Some(name))) if name == "factorial"));
+}
+
+#[test]
+fn deeply_nested_blocks() {
+    let limits = Limits {
+        stack_size: 280 << 10,
+        ..Limits::default()
+    };
+    // Far more blocks than fit in that little stack, and no calls among them
+    let nested = (0..150).fold(int(0), |inner, _| block(vec![], Some(inner)));
+
+    let output = run_with_limits(vec![println(nested)], limits);
+    assert!(matches!(output, Err(RuntimeError::StackOverflow(None))));
+}
+
+#[test]
+fn redefined_function() {
+    let output = run(vec![
+        function("one", &[], Some("int"), int(1)),
+        function("one", &[], Some("int"), int(2)),
+    ]);
+
+    assert!(matches!(output, Err(RuntimeError::FunctionRedefined(name)) if name == "one"));
+}
@@ -1,20 +1,21 @@
-use runner::ast::{CacauProgram, Expression, FunctionCall, HighLevelItem};
+use runner::ast::{CacauProgram, ExpressionKind, FunctionCall, HighLevelItem};
 
 #[test]
 fn test_hello() {
     // hello world AST
     let program = CacauProgram {
-        items: vec![HighLevelItem::Expr(Expression::FunctionCall(
-            FunctionCall {
+        items: vec![HighLevelItem::Expr(
+            ExpressionKind::FunctionCall(FunctionCall {
                 name: "println",
-                params: vec![Expression::StringLiteral("Hello, World!")],
-            },
-        ))],
+                params: vec![ExpressionKind::StringLiteral("Hello, World!".into()).into()],
+            })
+            .into(),
+        )],
     };
 
     // run
     let mut stdout = Vec::new();
-    runner::Runner::run(&program, &mut stdout);
+    runner::Runner::run(&program, &mut stdout).unwrap();
 
     // check output
     assert_eq!(String::from_utf8(stdout).unwrap(), "Hello, World!\n")
@@ -0,0 +1,255 @@
+use runner::{
+    ast::{
+        ArithmeticOperator, Assignee, ComparisonOperator, Expression, ExpressionKind, Function,
+        FunctionArgument, HighLevelItem, If, Lambda, Span,
+    },
+    RuntimeError,
+};
+
+mod common;
+
+use common::{arith, block, call, compare, int, let_, println, reassign, run, var};
+
+fn lambda<'a>(params: Vec<&'a str>, body: Expression<'a>) -> Expression<'a> {
+    ExpressionKind::Lambda(Box::new(Lambda { params, body })).into()
+}
+
+/// `fn <name> <params> -> <output> { <body> }`
+fn function<'a>(
+    name: &'a str,
+    params: Vec<(&'a str, &'a str)>,
+    output: &'a str,
+    body: Expression<'a>,
+) -> HighLevelItem<'a> {
+    HighLevelItem::Fn(Function {
+        public: false,
+        name,
+        receiver: false,
+        params: params
+            .into_iter()
+            .map(|(name, type_)| FunctionArgument {
+                name,
+                type_,
+                span: Span::default(),
+            })
+            .collect(),
+        output: Some(output),
+        body,
+        span: Span::default(),
+    })
+}
+
+#[test]
+fn stored_and_called() {
+    use ArithmeticOperator::*;
+
+    let output = run(vec![
+        HighLevelItem::Expr(let_(
+            "sum",
+            lambda(vec!["a", "b"], arith(var("a"), Add, var("b"))),
+        )),
+        println(call("sum", vec![int(1), int(2)])),
+        println(var("sum")),
+    ]);
+
+    assert_eq!(output.unwrap(), "3\n<function>\n");
+}
+
+#[test]
+fn passed_and_returned() {
+    use ArithmeticOperator::*;
+
+    let output = run(vec![
+        // fn make_adder n: int -> fn { fn x { x + n } }
+        function(
+            "make_adder",
+            vec![("n", "int")],
+            "fn",
+            lambda(vec!["x"], arith(var("x"), Add, var("n"))),
+        ),
+        // fn apply f: fn, x: int -> int { f(x) }
+        function(
+            "apply",
+            vec![("f", "fn"), ("x", "int")],
+            "int",
+            call("f", vec![var("x")]),
+        ),
+        HighLevelItem::Expr(let_("add_five", call("make_adder", vec![int(5)]))),
+        println(call("add_five", vec![int(1)])),
+        println(call("apply", vec![var("add_five"), int(10)])),
+        println(call(
+            "apply",
+            vec![
+                lambda(vec!["x"], arith(var("x"), Multiply, var("x"))),
+                int(7),
+            ],
+        )),
+    ]);
+
+    assert_eq!(output.unwrap(), "6\n15\n49\n");
+}
+
+/// Runs `statements` at the top level of a program, and then in the body of a function
+fn run_everywhere(
+    statements: impl Fn() -> Vec<Expression<'static>>,
+) -> [Result<String, RuntimeError>; 2] {
+    let top_level = statements().into_iter().map(HighLevelItem::Expr).collect();
+    let in_function = vec![
+        function("body", vec![], "int", block(statements(), Some(int(0)))),
+        HighLevelItem::Expr(call("body", vec![])),
+    ];
+
+    [run(top_level), run(in_function)]
+}
+
+fn println_<'a>(expression: Expression<'a>) -> Expression<'a> {
+    call("println", vec![expression])
+}
+
+#[test]
+fn captures_are_shared() {
+    // let k = 3; let times = fn x { x * k }; k = 100; println(times(2))
+    let statements = || {
+        vec![
+            let_("k", int(3)),
+            let_(
+                "times",
+                lambda(
+                    vec!["x"],
+                    arith(var("x"), ArithmeticOperator::Multiply, var("k")),
+                ),
+            ),
+            reassign(Assignee::Variable("k"), None, int(100)),
+            println_(call("times", vec![int(2)])),
+        ]
+    };
+
+    for output in run_everywhere(statements) {
+        assert_eq!(output.unwrap(), "200\n");
+    }
+}
+
+#[test]
+fn changes_to_captures_are_seen_outside() {
+    // let c = 0; let inc = fn { c += 1; c }; println(inc()); println(inc()); println(c)
+    let statements = || {
+        let increment = reassign(
+            Assignee::Variable("c"),
+            Some(ArithmeticOperator::Add),
+            int(1),
+        );
+        vec![
+            let_("c", int(0)),
+            let_(
+                "inc",
+                lambda(vec![], block(vec![increment], Some(var("c")))),
+            ),
+            println_(call("inc", vec![])),
+            println_(call("inc", vec![])),
+            println_(var("c")),
+        ]
+    };
+
+    for output in run_everywhere(statements) {
+        assert_eq!(output.unwrap(), "1\n2\n2\n");
+    }
+}
+
+#[test]
+fn captures_outlive_their_scope() {
+    // fn counter -> fn { let c = 0; fn { c += 1; c } }
+    let increment = reassign(
+        Assignee::Variable("c"),
+        Some(ArithmeticOperator::Add),
+        int(1),
+    );
+    let body = block(
+        vec![let_("c", int(0))],
+        Some(lambda(vec![], block(vec![increment], Some(var("c"))))),
+    );
+
+    let output = run(vec![
+        function("counter", vec![], "fn", body),
+        HighLevelItem::Expr(let_("first", call("counter", vec![]))),
+        HighLevelItem::Expr(let_("second", call("counter", vec![]))),
+        println(call("first", vec![])),
+        println(call("first", vec![])),
+        println(call("second", vec![])),
+    ]);
+
+    assert_eq!(output.unwrap(), "1\n2\n1\n");
+}
+
+#[test]
+fn calls_itself_by_its_name() {
+    use ArithmeticOperator::*;
+
+    // let fact = fn n { if n <= 1 { 1 } else { n * fact(n - 1) } }; println(fact(5))
+    let statements = || {
+        let body = ExpressionKind::If(Box::new(If {
+            condition: compare(var("n"), ComparisonOperator::LessEquals, int(1)),
+            then: int(1),
+            elifs: vec![],
+            otherwise: Some(arith(
+                var("n"),
+                Multiply,
+                call("fact", vec![arith(var("n"), Subtract, int(1))]),
+            )),
+        }));
+        vec![
+            let_("fact", lambda(vec!["n"], body.into())),
+            println_(call("fact", vec![int(5)])),
+        ]
+    };
+
+    for output in run_everywhere(statements) {
+        assert_eq!(output.unwrap(), "120\n");
+    }
+}
+
+#[test]
+fn later_variables_arent_captured() {
+    // let get = fn { later }; let later = 1; get()
+    let statements = || {
+        vec![
+            let_("get", lambda(vec![], var("later"))),
+            let_("later", int(1)),
+            call("get", vec![]),
+        ]
+    };
+
+    for output in run_everywhere(statements) {
+        assert!(
+            matches!(output, Err(RuntimeError::UndefinedVariable(ref name)) if name == "later")
+        );
+    }
+}
+
+#[test]
+fn variables_shadow_functions() {
+    let output = run(vec![
+        function("two", vec![], "int", int(2)),
+        println(call("two", vec![])),
+        HighLevelItem::Expr(let_("two", lambda(vec![], int(22)))),
+        println(call("two", vec![])),
+    ]);
+
+    assert_eq!(output.unwrap(), "2\n22\n");
+}
+
+#[test]
+fn arity_mismatch() {
+    let output = run(vec![
+        HighLevelItem::Expr(let_("identity", lambda(vec!["x"], var("x")))),
+        println(call("identity", vec![])),
+    ]);
+
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ArityMismatch {
+            ref function,
+            expected: 1,
+            found: 0,
+        }) if function == "identity"
+    ));
+}
@@ -0,0 +1,153 @@
+use runner::{
+    ast::{
+        ArithmeticOperation, ArithmeticOperator, Assignee, ComparisonOperator, Expression,
+        ExpressionKind, HighLevelItem, Reassignment,
+    },
+    RuntimeError,
+};
+
+mod common;
+
+use common::{call, compare, index, int, let_, list, println, run, var};
+
+fn add_assign<'a>(name: &'a str, expression: Expression<'a>) -> HighLevelItem<'a> {
+    HighLevelItem::Expr(
+        ExpressionKind::Reassignment(Box::new(Reassignment {
+            target: Assignee::Variable(name),
+            op: Some(ArithmeticOperator::Add),
+            expression,
+        }))
+        .into(),
+    )
+}
+
+#[test]
+fn literals_and_printing() {
+    let output = run(vec![
+        println(list(vec![int(5), int(10), int(15)])),
+        println(call("list", vec![])),
+        println(list(vec![
+            ExpressionKind::StringLiteral("a".into()).into(),
+            ExpressionKind::CharLiteral('b').into(),
+            list(vec![ExpressionKind::FloatLiteral(1.0).into()]),
+        ])),
+    ]);
+
+    assert_eq!(
+        output.unwrap(),
+        "[5, 10, 15]\n[]\n[\"a\", 'b', [1.00000]]\n"
+    );
+}
+
+#[test]
+fn indexing() {
+    let output = run(vec![
+        HighLevelItem::Expr(let_("lista", list(vec![int(5), int(10), int(15)]))),
+        println(index(var("lista"), 0)),
+        println(index(var("lista"), 2)),
+        println(index(var("lista"), -1)),
+        println(index(var("lista"), -3)),
+    ]);
+
+    assert_eq!(output.unwrap(), "5\n15\n15\n5\n");
+}
+
+#[test]
+fn index_out_of_bounds() {
+    for position in [3, -4, i64::MIN] {
+        let output = run(vec![println(index(
+            list(vec![int(1), int(2), int(3)]),
+            position,
+        ))]);
+
+        assert!(matches!(
+            output,
+            Err(RuntimeError::IndexOutOfBounds { index, len: 3 }) if index == position
+        ));
+    }
+
+    let output = run(vec![println(index(int(1), 0))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::UnexpectedType {
+            expected: "list or tuple",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn push_and_extend() {
+    let output = run(vec![
+        HighLevelItem::Expr(let_("lista", call("list", vec![]))),
+        add_assign("lista", int(300)),
+        add_assign("lista", list(vec![int(1), int(2)])),
+        add_assign("lista", list(vec![list(vec![])])),
+        println(var("lista")),
+        // `+` concatenates without changing its operands
+        println(
+            ExpressionKind::ArithOperation(Box::new(ArithmeticOperation {
+                left: var("lista"),
+                op: ArithmeticOperator::Add,
+                right: list(vec![int(4)]),
+            }))
+            .into(),
+        ),
+        println(var("lista")),
+    ]);
+
+    assert_eq!(
+        output.unwrap(),
+        "[300, 1, 2, []]\n[300, 1, 2, [], 4]\n[300, 1, 2, []]\n"
+    );
+}
+
+#[test]
+fn copies_are_independent() {
+    let output = run(vec![
+        HighLevelItem::Expr(let_("original", list(vec![int(1)]))),
+        HighLevelItem::Expr(let_("copy", var("original"))),
+        add_assign("copy", int(2)),
+        println(var("original")),
+        println(var("copy")),
+    ]);
+
+    assert_eq!(output.unwrap(), "[1]\n[1, 2]\n");
+}
+
+#[test]
+fn comparisons() {
+    use ComparisonOperator::*;
+
+    let output = run(vec![
+        println(compare(
+            list(vec![int(1), list(vec![int(2)])]),
+            Equals,
+            list(vec![int(1), list(vec![int(2)])]),
+        )),
+        println(compare(list(vec![int(1)]), NotEquals, list(vec![]))),
+        println(compare(
+            list(vec![int(1), int(2)]),
+            Less,
+            list(vec![int(1), int(3)]),
+        )),
+        println(compare(
+            list(vec![int(1), int(2)]),
+            Greater,
+            list(vec![int(1)]),
+        )),
+        // Enums aren't ordered, but equal ones are as in `==`
+        println(compare(
+            list(vec![var("None")]),
+            LessEquals,
+            list(vec![var("None")]),
+        )),
+        println(compare(
+            list(vec![var("None")]),
+            Less,
+            list(vec![var("None")]),
+        )),
+    ]);
+
+    assert_eq!(output.unwrap(), "true\ntrue\ntrue\ntrue\ntrue\nfalse\n");
+}
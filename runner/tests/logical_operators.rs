@@ -0,0 +1,94 @@
+use runner::{
+    ast::{BooleanOperation, BooleanOperator, Expression, ExpressionKind, FunctionCall},
+    RuntimeError,
+};
+
+mod common;
+
+use common::{bool_, println, run};
+
+fn boolean<'a>(left: Expression<'a>, op: BooleanOperator, right: Expression<'a>) -> Expression<'a> {
+    ExpressionKind::BoolOperation(Box::new(BooleanOperation { left, op, right })).into()
+}
+
+/// Prints the result of `op` for every combination of operands
+fn truth_table(op: fn() -> BooleanOperator) -> String {
+    let items = [(false, false), (false, true), (true, false), (true, true)]
+        .into_iter()
+        .map(|(left, right)| println(boolean(bool_(left), op(), bool_(right))))
+        .collect();
+
+    run(items).unwrap()
+}
+
+#[test]
+fn truth_tables() {
+    assert_eq!(
+        truth_table(|| BooleanOperator::And),
+        "false\nfalse\nfalse\ntrue\n"
+    );
+    assert_eq!(
+        truth_table(|| BooleanOperator::Or),
+        "false\ntrue\ntrue\ntrue\n"
+    );
+    assert_eq!(
+        truth_table(|| BooleanOperator::Xor),
+        "false\ntrue\ntrue\nfalse\n"
+    );
+    assert_eq!(
+        truth_table(|| BooleanOperator::Nand),
+        "true\ntrue\ntrue\nfalse\n"
+    );
+    assert_eq!(
+        truth_table(|| BooleanOperator::Nor),
+        "true\nfalse\nfalse\nfalse\n"
+    );
+}
+
+#[test]
+fn operands_must_be_booleans() {
+    let output = run(vec![println(boolean(
+        bool_(true),
+        BooleanOperator::Xor,
+        ExpressionKind::IntegerLiteral(1).into(),
+    ))]);
+
+    assert!(matches!(
+        output,
+        Err(RuntimeError::TypeMismatch { op: "xor", .. })
+    ));
+}
+
+#[test]
+fn and_or_short_circuit() {
+    let panic = || -> Expression {
+        ExpressionKind::FunctionCall(FunctionCall {
+            name: "panic",
+            params: vec![],
+        })
+        .into()
+    };
+
+    let output = run(vec![
+        println(boolean(bool_(true), BooleanOperator::Or, panic())),
+        println(boolean(bool_(false), BooleanOperator::And, panic())),
+        println(boolean(bool_(true), BooleanOperator::Nor, panic())),
+        println(boolean(bool_(false), BooleanOperator::Nand, panic())),
+    ]);
+    assert_eq!(output.unwrap(), "true\nfalse\nfalse\ntrue\n");
+
+    // The right operand still runs when the left one doesn't decide the result
+    let output = run(vec![println(boolean(
+        bool_(false),
+        BooleanOperator::Or,
+        panic(),
+    ))]);
+    assert!(matches!(output, Err(RuntimeError::Panicked)));
+
+    let output = run(vec![println(boolean(
+        bool_(true),
+        BooleanOperator::Xor,
+        panic(),
+    ))]);
+    assert!(matches!(output, Err(RuntimeError::Panicked)));
+}
@@ -0,0 +1,301 @@
+use runner::{
+    ast::{
+        ComparisonOperation, ComparisonOperator, Enum, Expression, ExpressionKind, FieldPattern,
+        FieldValue, HighLevelItem, Match, MatchArm, Pattern, Span, StructLiteral, StructPattern,
+        Variant, VariantPattern,
+    },
+    mem::Value,
+    RuntimeError,
+};
+
+mod common;
+
+use common::{call, int, point_struct, println, run, string, tuple, var};
+
+fn literal(integer: i64) -> Pattern<'static> {
+    Pattern::Literal(int(integer))
+}
+
+fn variant<'a>(variant: &'a str, payload: Option<Pattern<'a>>) -> Pattern<'a> {
+    Pattern::Variant(VariantPattern {
+        ty: None,
+        variant,
+        payload: payload.map(Box::new),
+    })
+}
+
+/// `<pattern> if <guard> => <body>`
+fn arm<'a>(
+    pattern: Pattern<'a>,
+    guard: Option<Expression<'a>>,
+    body: Expression<'a>,
+) -> MatchArm<'a> {
+    MatchArm {
+        pattern,
+        guard,
+        body,
+        span: Span::default(),
+    }
+}
+
+fn match_<'a>(scrutinee: Expression<'a>, arms: Vec<MatchArm<'a>>) -> Expression<'a> {
+    ExpressionKind::Match(Box::new(Match { scrutinee, arms })).into()
+}
+
+/// `enum User { Admin(string), Guest, Banned(Option<int>) }`
+fn user_enum<'a>() -> HighLevelItem<'a> {
+    let variant = |name, payload| Variant {
+        name,
+        payload,
+        span: Span::default(),
+    };
+
+    HighLevelItem::Enum(Enum {
+        public: false,
+        name: "User",
+        variants: vec![
+            variant("Admin", Some("string")),
+            variant("Guest", None),
+            variant("Banned", Some("Option<int>")),
+        ],
+        span: Span::default(),
+    })
+}
+
+fn point<'a>(x: i64, y: i64) -> Expression<'a> {
+    ExpressionKind::StructLiteral(StructLiteral {
+        name: "Point",
+        fields: vec![
+            FieldValue {
+                name: "x",
+                expression: int(x),
+            },
+            FieldValue {
+                name: "y",
+                expression: int(y),
+            },
+        ],
+    })
+    .into()
+}
+
+#[test]
+fn literals_wildcards_and_guards() {
+    let describe = |value| {
+        let greater = ExpressionKind::CompOperation(Box::new(ComparisonOperation {
+            left: var("n"),
+            op: ComparisonOperator::Greater,
+            right: int(10),
+        }));
+
+        println(match_(
+            int(value),
+            vec![
+                arm(literal(0), None, string("zero")),
+                arm(literal(-1), None, string("minus one")),
+                arm(Pattern::Identifier("n"), Some(greater.into()), var("n")),
+                arm(Pattern::Wildcard, None, string("other")),
+            ],
+        ))
+    };
+
+    let output = run(vec![describe(0), describe(-1), describe(42), describe(7)]);
+
+    assert_eq!(output.unwrap(), "zero\nminus one\n42\nother\n");
+}
+
+#[test]
+fn variants() {
+    let describe = |value| {
+        println(match_(
+            value,
+            vec![
+                arm(
+                    variant("Admin", Some(Pattern::Identifier("name"))),
+                    None,
+                    var("name"),
+                ),
+                arm(variant("Banned", Some(variant("None", None))), None, int(0)),
+                arm(
+                    variant("Banned", Some(variant("Some", Some(Pattern::Wildcard)))),
+                    None,
+                    int(1),
+                ),
+                arm(Pattern::Identifier("Guest"), None, string("guest")),
+            ],
+        ))
+    };
+
+    let output = run(vec![
+        user_enum(),
+        describe(call("Admin", vec![string("joao")])),
+        describe(var("Guest")),
+        describe(call("Banned", vec![var("None")])),
+        describe(call("Banned", vec![call("Some", vec![int(7)])])),
+    ]);
+
+    assert_eq!(output.unwrap(), "joao\nguest\n0\n1\n");
+}
+
+#[test]
+fn tuples_and_structs() {
+    let output = run(vec![
+        point_struct(),
+        println(match_(
+            tuple(vec![int(1), int(2)]),
+            vec![
+                arm(
+                    Pattern::Tuple(vec![literal(2), Pattern::Identifier("y")]),
+                    None,
+                    var("y"),
+                ),
+                arm(
+                    Pattern::Tuple(vec![Pattern::Identifier("x"), literal(2)]),
+                    None,
+                    var("x"),
+                ),
+                arm(Pattern::Wildcard, None, int(0)),
+            ],
+        )),
+        println(match_(
+            point(0, 5),
+            vec![
+                arm(
+                    Pattern::Struct(StructPattern {
+                        name: "Point",
+                        fields: vec![FieldPattern {
+                            name: "x",
+                            pattern: literal(1),
+                        }],
+                        rest: true,
+                    }),
+                    None,
+                    string("x is one"),
+                ),
+                arm(
+                    Pattern::Struct(StructPattern {
+                        name: "Point",
+                        fields: vec![
+                            FieldPattern {
+                                name: "x",
+                                pattern: literal(0),
+                            },
+                            FieldPattern {
+                                name: "y",
+                                pattern: Pattern::Identifier("y"),
+                            },
+                        ],
+                        rest: false,
+                    }),
+                    None,
+                    var("y"),
+                ),
+            ],
+        )),
+    ]);
+
+    assert_eq!(output.unwrap(), "1\n5\n");
+}
+
+#[test]
+fn bindings_only_last_for_their_arm() {
+    let output = run(vec![
+        HighLevelItem::Expr(match_(
+            int(1),
+            vec![arm(Pattern::Identifier("n"), None, var("n"))],
+        )),
+        println(var("n")),
+    ]);
+
+    assert!(matches!(output, Err(RuntimeError::UndefinedVariable(name)) if name == "n"));
+}
+
+#[test]
+fn no_arm_matches() {
+    let output = run(vec![println(match_(
+        int(3),
+        vec![arm(literal(1), None, int(1)), arm(literal(2), None, int(2))],
+    ))]);
+
+    assert!(matches!(
+        output,
+        Err(RuntimeError::NoMatch(Value::Integer(3)))
+    ));
+}
+
+#[test]
+fn struct_patterns_give_every_field() {
+    let output = run(vec![
+        point_struct(),
+        println(match_(
+            point(0, 5),
+            vec![arm(
+                Pattern::Struct(StructPattern {
+                    name: "Point",
+                    fields: vec![FieldPattern {
+                        name: "x",
+                        pattern: Pattern::Wildcard,
+                    }],
+                    rest: false,
+                }),
+                None,
+                int(0),
+            )],
+        )),
+    ]);
+
+    assert!(matches!(
+        output,
+        Err(RuntimeError::MissingField { ref field, .. }) if field == "y"
+    ));
+}
+
+#[test]
+fn matches_over_enums_are_exhaustive() {
+    let with_arms = |arms| {
+        run(vec![
+            user_enum(),
+            // Nothing runs when the check fails
+            println(int(1)),
+            HighLevelItem::Expr(match_(var("Guest"), arms)),
+        ])
+    };
+
+    let output = with_arms(vec![
+        arm(variant("Admin", Some(Pattern::Wildcard)), None, int(0)),
+        arm(
+            variant("Banned", Some(variant("Some", Some(Pattern::Wildcard)))),
+            None,
+            int(0),
+        ),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::NonExhaustiveMatch(missing)) if missing == ["Guest", "Banned(None)"]
+    ));
+
+    // An arm with a guard may not be taken
+    let output = with_arms(vec![
+        arm(Pattern::Identifier("Guest"), None, int(0)),
+        arm(
+            variant("Banned", Some(Pattern::Identifier("reason"))),
+            None,
+            int(0),
+        ),
+        arm(
+            variant("Admin", Some(Pattern::Wildcard)),
+            Some(ExpressionKind::BooleanLiteral(true).into()),
+            int(0),
+        ),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::NonExhaustiveMatch(missing)) if missing == ["Admin(_)"]
+    ));
+
+    let output = with_arms(vec![
+        arm(Pattern::Identifier("Guest"), None, int(0)),
+        arm(Pattern::Identifier("other"), None, int(0)),
+    ]);
+    assert_eq!(output.unwrap(), "1\n");
+}
@@ -0,0 +1,309 @@
+use runner::{
+    ast::{
+        ArithmeticOperator, Assignee, Expression, ExpressionKind, FieldValue, Function,
+        FunctionArgument, HighLevelItem, Impl, MethodCall, Reassignment, Span, Struct, StructField,
+        StructLiteral, VariantLiteral,
+    },
+    RuntimeError,
+};
+
+mod common;
+
+use common::{block, field, index, int, let_, list, println, run, tuple, var};
+
+fn method<'a>(
+    receiver: Expression<'a>,
+    name: &'a str,
+    params: Vec<Expression<'a>>,
+) -> Expression<'a> {
+    ExpressionKind::MethodCall(Box::new(MethodCall {
+        receiver,
+        name,
+        params,
+    }))
+    .into()
+}
+
+/// `<ty>::<name>(<params>)`
+fn type_call<'a>(ty: &'a str, name: &'a str, params: Vec<Expression<'a>>) -> Expression<'a> {
+    ExpressionKind::VariantLiteral(Box::new(VariantLiteral { ty, name, params })).into()
+}
+
+fn counter<'a>(name: &'a str, count: Expression<'a>) -> Expression<'a> {
+    ExpressionKind::StructLiteral(StructLiteral {
+        name,
+        fields: vec![FieldValue {
+            name: "count",
+            expression: count,
+        }],
+    })
+    .into()
+}
+
+/// `name.count += 1`
+fn increment(name: &str) -> Expression<'_> {
+    ExpressionKind::Reassignment(Box::new(Reassignment {
+        target: Assignee::Field {
+            base: Box::new(Assignee::Variable(name)),
+            field: "count",
+        },
+        op: Some(ArithmeticOperator::Add),
+        expression: int(1),
+    }))
+    .into()
+}
+
+fn function<'a>(
+    name: &'a str,
+    receiver: bool,
+    params: &[&'a str],
+    body: Expression<'a>,
+) -> Function<'a> {
+    Function {
+        public: false,
+        name,
+        receiver,
+        params: params
+            .iter()
+            .map(|name| FunctionArgument {
+                name,
+                type_: "int",
+                span: Span::default(),
+            })
+            .collect(),
+        output: None,
+        body,
+        span: Span::default(),
+    }
+}
+
+/// ```text
+/// struct Counter { count: int }
+///
+/// impl Counter {
+///     // Changes the counter it's called on
+///     fn increment self { self.count += 1; }
+///     // Changes a copy of the counter
+///     fn incremented self { let copy = self; copy.count += 1; copy }
+///     fn with_count self, count: int { Self { count: count } }
+///     fn zero { 0 }
+///     fn new count: int { Self { count: count } }
+/// }
+/// ```
+fn counter_type<'a>() -> Vec<HighLevelItem<'a>> {
+    let struct_ = Struct {
+        public: false,
+        name: "Counter",
+        fields: vec![StructField {
+            public: false,
+            name: "count",
+            type_: "int",
+            span: Span::default(),
+        }],
+        span: Span::default(),
+    };
+
+    let methods = vec![
+        function("increment", true, &[], block(vec![increment("self")], None)),
+        function(
+            "incremented",
+            true,
+            &[],
+            block(
+                vec![let_("copy", var("self")), increment("copy")],
+                Some(var("copy")),
+            ),
+        ),
+        function(
+            "with_count",
+            true,
+            &["count"],
+            counter("Self", var("count")),
+        ),
+        function("zero", false, &[], int(0)),
+        function("new", false, &["count"], counter("Self", var("count"))),
+    ];
+
+    vec![
+        HighLevelItem::Struct(struct_),
+        HighLevelItem::Impl(Impl {
+            ty: "Counter",
+            methods,
+            span: Span::default(),
+        }),
+    ]
+}
+
+#[test]
+fn changes_to_self_are_written_back() {
+    let mut items = counter_type();
+    items.extend([
+        HighLevelItem::Expr(let_("c", counter("Counter", int(0)))),
+        HighLevelItem::Expr(method(var("c"), "increment", vec![])),
+        HighLevelItem::Expr(method(var("c"), "increment", vec![])),
+        println(var("c")),
+        // Unless `self` is copied first
+        println(method(var("c"), "incremented", vec![])),
+        println(var("c")),
+    ]);
+
+    assert_eq!(
+        run(items).unwrap(),
+        "Counter { count: 2 }\nCounter { count: 3 }\nCounter { count: 2 }\n"
+    );
+}
+
+#[test]
+fn functions_without_self() {
+    let mut items = counter_type();
+    items.extend([
+        println(type_call("Counter", "zero", vec![])),
+        println(type_call("Counter", "new", vec![int(4)])),
+    ]);
+
+    assert_eq!(run(items).unwrap(), "0\nCounter { count: 4 }\n");
+}
+
+#[test]
+fn write_back_to_fields() {
+    let outer = || {
+        ExpressionKind::StructLiteral(StructLiteral {
+            name: "Outer",
+            fields: vec![FieldValue {
+                name: "inner",
+                expression: counter("Counter", int(5)),
+            }],
+        })
+        .into()
+    };
+
+    let mut items = counter_type();
+    items.extend([
+        HighLevelItem::Struct(Struct {
+            public: false,
+            name: "Outer",
+            fields: vec![StructField {
+                public: false,
+                name: "inner",
+                type_: "Counter",
+                span: Span::default(),
+            }],
+            span: Span::default(),
+        }),
+        HighLevelItem::Expr(let_("o", outer())),
+        HighLevelItem::Expr(method(field(var("o"), "inner"), "increment", vec![])),
+        println(var("o")),
+        // Values that aren't stored anywhere are only changed for the method
+        HighLevelItem::Expr(method(counter("Counter", int(0)), "increment", vec![])),
+    ]);
+
+    assert_eq!(
+        run(items).unwrap(),
+        "Outer { inner: Counter { count: 6 } }\n"
+    );
+}
+
+#[test]
+fn write_back_to_items() {
+    let mut items = counter_type();
+    items.extend([
+        HighLevelItem::Expr(let_(
+            "counters",
+            list(vec![counter("Counter", int(0)), counter("Counter", int(1))]),
+        )),
+        HighLevelItem::Expr(let_("copy", var("counters"))),
+        HighLevelItem::Expr(method(index(var("counters"), -1), "increment", vec![])),
+        println(var("counters")),
+        println(var("copy")),
+        HighLevelItem::Expr(let_(
+            "pair",
+            tuple(vec![list(vec![counter("Counter", int(7))]), int(0)]),
+        )),
+        HighLevelItem::Expr(method(index(index(var("pair"), 0), 0), "increment", vec![])),
+        println(var("pair")),
+    ]);
+
+    assert_eq!(
+        run(items).unwrap(),
+        "[Counter { count: 0 }, Counter { count: 2 }]\n\
+         [Counter { count: 0 }, Counter { count: 1 }]\n\
+         ([Counter { count: 8 }], 0)\n"
+    );
+}
+
+#[test]
+fn self_type() {
+    let mut items = counter_type();
+    items.push(println(method(
+        counter("Counter", int(0)),
+        "with_count",
+        vec![int(7)],
+    )));
+    assert_eq!(run(items).unwrap(), "Counter { count: 7 }\n");
+
+    // `Self` only means something inside methods
+    let mut items = counter_type();
+    items.push(println(counter("Self", int(0))));
+    assert!(matches!(
+        run(items),
+        Err(RuntimeError::UndefinedType(ref name)) if name == "Self"
+    ));
+}
+
+#[test]
+fn method_errors() {
+    let mut items = counter_type();
+    items.push(println(method(
+        counter("Counter", int(0)),
+        "with_count",
+        vec![],
+    )));
+    assert!(matches!(
+        run(items),
+        Err(RuntimeError::ArityMismatch {
+            expected: 1,
+            found: 0,
+            ..
+        })
+    ));
+
+    // Functions without `self` aren't methods, and methods aren't called on the type
+    let mut items = counter_type();
+    items.push(println(method(counter("Counter", int(0)), "zero", vec![])));
+    assert!(matches!(
+        run(items),
+        Err(RuntimeError::ReceiverMismatch { ref function, takes_self: false })
+            if function == "Counter::zero"
+    ));
+
+    let mut items = counter_type();
+    items.push(println(type_call("Counter", "increment", vec![])));
+    assert!(matches!(
+        run(items),
+        Err(RuntimeError::ReceiverMismatch { ref function, takes_self: true })
+            if function == "Counter::increment"
+    ));
+
+    for call in [
+        method(counter("Counter", int(0)), "decrement", vec![]),
+        type_call("Counter", "decrement", vec![]),
+    ] {
+        let mut items = counter_type();
+        items.push(println(call));
+        assert!(matches!(
+            run(items),
+            Err(RuntimeError::UndefinedMethod { ref type_name, ref method })
+                if type_name == "Counter" && method == "decrement"
+        ));
+    }
+
+    let items = vec![HighLevelItem::Impl(Impl {
+        ty: "Nothing",
+        methods: vec![],
+        span: Span::default(),
+    })];
+    assert!(matches!(
+        run(items),
+        Err(RuntimeError::UndefinedType(ref name)) if name == "Nothing"
+    ));
+}
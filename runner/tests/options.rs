@@ -0,0 +1,78 @@
+use runner::ast::{
+    Assignment, ComparisonOperator, Expression, ExpressionKind, HighLevelItem, Pattern,
+};
+
+mod common;
+
+use common::{call, compare, get, int, println, run, var};
+
+fn some(value: Expression) -> Expression {
+    call("Some", vec![value])
+}
+
+#[test]
+fn printing() {
+    let output = run(vec![
+        println(some(int(10))),
+        println(some(ExpressionKind::StringLiteral("a".into()).into())),
+        println(some(some(var("None")))),
+        println(var("None")),
+    ]);
+
+    assert_eq!(
+        output.unwrap(),
+        "Some(10)\nSome(\"a\")\nSome(Some(None))\nNone\n"
+    );
+}
+
+#[test]
+fn equality() {
+    use ComparisonOperator::*;
+
+    let output = run(vec![
+        println(compare(some(int(1)), Equals, some(int(1)))),
+        println(compare(some(int(1)), Equals, some(int(2)))),
+        println(compare(some(int(1)), NotEquals, var("None"))),
+        println(compare(var("None"), Equals, var("None"))),
+    ]);
+
+    assert_eq!(output.unwrap(), "true\nfalse\ntrue\ntrue\n");
+}
+
+#[test]
+fn get_on_lists_and_strings() {
+    let list = || ExpressionKind::ListLiteral(vec![int(5), int(10)]).into();
+    let string = || ExpressionKind::StringLiteral("ação".into()).into();
+
+    let output = run(vec![
+        println(get(list(), 1)),
+        println(get(list(), -2)),
+        println(get(list(), 2)),
+        println(get(string(), 1)),
+        println(get(string(), -1)),
+        println(get(string(), 4)),
+        println(get(ExpressionKind::StringLiteral("".into()).into(), 0)),
+    ]);
+
+    assert_eq!(
+        output.unwrap(),
+        "Some(10)\nSome(5)\nNone\nSome('ç')\nSome('o')\nNone\nNone\n"
+    );
+}
+
+#[test]
+fn none_can_be_shadowed() {
+    let output = run(vec![
+        HighLevelItem::Expr(
+            ExpressionKind::Assignment(Box::new(Assignment {
+                pattern: Pattern::Identifier("None"),
+                type_annotation: None,
+                expression: int(0),
+            }))
+            .into(),
+        ),
+        println(var("None")),
+    ]);
+
+    assert_eq!(output.unwrap(), "0\n");
+}
@@ -0,0 +1,131 @@
+use runner::{
+    ast::{ArithmeticOperator, Assignee, Block, ExpressionKind, Function, HighLevelItem, Span},
+    mem::Value,
+    RuntimeError,
+};
+
+mod common;
+
+use common::{call, float, int, let_, println, reassign, run, var};
+
+#[test]
+fn reassignment() {
+    let output = run(vec![
+        HighLevelItem::Expr(let_("a", int(10))),
+        HighLevelItem::Expr(reassign(Assignee::Variable("a"), None, int(20))),
+        println(var("a")),
+    ]);
+
+    assert_eq!(output.unwrap(), "20\n");
+}
+
+#[test]
+fn compound_assignments() {
+    use ArithmeticOperator::*;
+
+    let output = run(vec![
+        HighLevelItem::Expr(let_("i", int(7))),
+        HighLevelItem::Expr(reassign(Assignee::Variable("i"), Some(Add), int(1))),
+        println(var("i")),
+        HighLevelItem::Expr(reassign(Assignee::Variable("i"), Some(Subtract), int(2))),
+        println(var("i")),
+        HighLevelItem::Expr(reassign(Assignee::Variable("i"), Some(Multiply), int(3))),
+        println(var("i")),
+        HighLevelItem::Expr(reassign(Assignee::Variable("i"), Some(Divide), int(4))),
+        println(var("i")),
+        HighLevelItem::Expr(reassign(Assignee::Variable("i"), Some(Modulo), int(3))),
+        println(var("i")),
+        HighLevelItem::Expr(reassign(Assignee::Variable("i"), Some(Power), int(5))),
+        println(var("i")),
+    ]);
+
+    assert_eq!(output.unwrap(), "8\n6\n18\n4\n1\n1\n");
+}
+
+#[test]
+fn reassignments_reach_outer_scopes() {
+    let block = ExpressionKind::Block(Box::new(Block {
+        statements: vec![reassign(
+            Assignee::Variable("total"),
+            Some(ArithmeticOperator::Add),
+            int(1),
+        )],
+        tail: None,
+    }));
+    let increment = HighLevelItem::Fn(Function {
+        public: false,
+        name: "increment",
+        receiver: false,
+        params: vec![],
+        output: None,
+        body: block.into(),
+        span: Span::default(),
+    });
+
+    let output = run(vec![
+        increment,
+        HighLevelItem::Expr(let_("total", int(0))),
+        HighLevelItem::Expr(call("increment", vec![])),
+        HighLevelItem::Expr(call("increment", vec![])),
+        println(var("total")),
+    ]);
+
+    assert_eq!(output.unwrap(), "2\n");
+}
+
+#[test]
+fn undeclared_variable() {
+    let output = run(vec![HighLevelItem::Expr(reassign(
+        Assignee::Variable("nope"),
+        None,
+        int(1),
+    ))]);
+
+    assert!(matches!(output, Err(RuntimeError::UndefinedVariable(name)) if name == "nope"));
+}
+
+#[test]
+fn type_is_kept() {
+    // A float stays a float
+    let output = run(vec![
+        HighLevelItem::Expr(let_("area", float(5.0))),
+        HighLevelItem::Expr(reassign(
+            Assignee::Variable("area"),
+            Some(ArithmeticOperator::Multiply),
+            int(2),
+        )),
+        println(var("area")),
+    ]);
+    assert_eq!(output.unwrap(), "10.00000\n");
+
+    // But an int can't become one
+    let output = run(vec![
+        HighLevelItem::Expr(let_("area", int(5))),
+        HighLevelItem::Expr(reassign(
+            Assignee::Variable("area"),
+            Some(ArithmeticOperator::Multiply),
+            float(2.0),
+        )),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ReassignmentTypeMismatch {
+            expected: "int",
+            found: Value::Float(_),
+            ..
+        })
+    ));
+
+    let output = run(vec![
+        HighLevelItem::Expr(let_("text", int(5))),
+        HighLevelItem::Expr(reassign(
+            Assignee::Variable("text"),
+            None,
+            ExpressionKind::StringLiteral("five".into()).into(),
+        )),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ReassignmentTypeMismatch { ref name, .. }) if name == "text"
+    ));
+}
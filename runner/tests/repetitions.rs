@@ -0,0 +1,83 @@
+use runner::{
+    ast::{ArithmeticOperation, ArithmeticOperator, Expression, ExpressionKind},
+    Limits, RuntimeError,
+};
+
+mod common;
+
+use common::{int, list, println, run_with_limits, string};
+
+fn multiply<'a>(left: Expression<'a>, right: Expression<'a>) -> Expression<'a> {
+    ExpressionKind::ArithOperation(Box::new(ArithmeticOperation {
+        left,
+        op: ArithmeticOperator::Multiply,
+        right,
+    }))
+    .into()
+}
+
+#[test]
+fn strings_and_lists() {
+    let output = run_with_limits(
+        vec![
+            println(multiply(string("ab"), int(3))),
+            println(multiply(int(2), string("ab"))),
+            println(multiply(string("ab"), int(0))),
+            println(multiply(list(vec![int(1), int(2)]), int(2))),
+            println(multiply(int(0), list(vec![int(1)]))),
+        ],
+        Limits::default(),
+    );
+
+    assert_eq!(output.unwrap(), "ababab\nabab\n\n[1, 2, 1, 2]\n[]\n");
+}
+
+#[test]
+fn negative_counts() {
+    let output = run_with_limits(
+        vec![println(multiply(string("ab"), int(-1)))],
+        Limits::default(),
+    );
+    assert!(matches!(output, Err(RuntimeError::NegativeRepetition(-1))));
+
+    let output = run_with_limits(
+        vec![println(multiply(int(-2), list(vec![])))],
+        Limits::default(),
+    );
+    assert!(matches!(output, Err(RuntimeError::NegativeRepetition(-2))));
+}
+
+#[test]
+fn lengths_are_limited() {
+    let limits = Limits {
+        max_repeat_len: 6,
+        ..Limits::default()
+    };
+
+    let output = run_with_limits(vec![println(multiply(string("ab"), int(3)))], limits);
+    assert_eq!(output.unwrap(), "ababab\n");
+
+    let output = run_with_limits(vec![println(multiply(int(4), string("ab")))], limits);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::RepetitionTooLong {
+            len: 2,
+            count: 4,
+            max: 6
+        })
+    ));
+
+    // Even when the length doesn't fit in a usize
+    let output = run_with_limits(
+        vec![println(multiply(list(vec![int(1), int(2)]), int(i64::MAX)))],
+        limits,
+    );
+    assert!(matches!(
+        output,
+        Err(RuntimeError::RepetitionTooLong { .. })
+    ));
+
+    // Empty strings and lists stay empty
+    let output = run_with_limits(vec![println(multiply(string(""), int(i64::MAX)))], limits);
+    assert_eq!(output.unwrap(), "\n");
+}
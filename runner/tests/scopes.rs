@@ -0,0 +1,112 @@
+use runner::{
+    ast::{Expression, ExpressionKind, HighLevelItem, If},
+    mem::{SymbolTable, Value},
+    RuntimeError,
+};
+
+mod common;
+
+use common::{call, function, int, let_, println, run, var};
+
+fn if_true(then: Expression) -> Expression {
+    ExpressionKind::If(Box::new(If {
+        condition: ExpressionKind::BooleanLiteral(true).into(),
+        then,
+        elifs: vec![],
+        otherwise: None,
+    }))
+    .into()
+}
+
+fn get_int(table: &SymbolTable, name: &str) -> Option<i64> {
+    match table.get_value(name) {
+        Some(Value::Integer(integer)) => Some(integer),
+        Some(other) => panic!("expected an integer, found {:?}", other),
+        None => None,
+    }
+}
+
+#[test]
+fn symbol_table_scopes() {
+    let mut table = SymbolTable::default();
+    table.create_var("x", Value::Integer(1));
+
+    table.push_scope();
+    assert_eq!(get_int(&table, "x"), Some(1));
+    table.create_var("x", Value::Integer(2));
+    table.create_var("y", Value::Integer(3));
+    assert_eq!(get_int(&table, "x"), Some(2));
+    table.pop_scope();
+
+    assert_eq!(get_int(&table, "x"), Some(1));
+    assert_eq!(get_int(&table, "y"), None);
+}
+
+#[test]
+fn symbol_table_frames() {
+    let mut table = SymbolTable::default();
+    table.create_var("global", Value::Integer(1));
+
+    table.push_frame();
+    table.create_var("caller", Value::Integer(2));
+
+    table.push_frame();
+    assert_eq!(get_int(&table, "global"), Some(1));
+    assert_eq!(get_int(&table, "caller"), None);
+    table.pop_frame();
+
+    assert_eq!(get_int(&table, "caller"), Some(2));
+    table.pop_frame();
+    assert_eq!(get_int(&table, "caller"), None);
+}
+
+#[test]
+fn if_branches_have_their_own_scope() {
+    let output = run(vec![
+        HighLevelItem::Expr(let_("x", int(1))),
+        HighLevelItem::Expr(if_true(let_("x", int(2)))),
+        println(var("x")),
+        HighLevelItem::Expr(if_true(let_("y", int(3)))),
+        println(var("y")),
+    ]);
+
+    assert!(matches!(output, Err(RuntimeError::UndefinedVariable(name)) if name == "y"));
+}
+
+#[test]
+fn inner_bindings_shadow_outer_ones() {
+    let output = run(vec![
+        HighLevelItem::Expr(let_("x", int(1))),
+        HighLevelItem::Expr(if_true(let_("x", int(2)))),
+        HighLevelItem::Expr(if_true(call("println", vec![var("x")]))),
+        println(var("x")),
+    ]);
+
+    assert_eq!(output.unwrap(), "1\n1\n");
+}
+
+#[test]
+fn functions_see_globals_but_not_their_callers_variables() {
+    let output = run(vec![
+        function("get_global", &[], None, var("global")),
+        function("outer", &["local"], None, call("inner", vec![])),
+        function("inner", &[], None, var("local")),
+        HighLevelItem::Expr(let_("global", int(10))),
+        println(call("get_global", vec![])),
+        println(call("outer", vec![int(20)])),
+    ]);
+
+    assert!(matches!(output, Err(RuntimeError::UndefinedVariable(name)) if name == "local"));
+}
+
+#[test]
+fn arguments_shadow_globals() {
+    let output = run(vec![
+        function("identity", &["x"], None, var("x")),
+        HighLevelItem::Expr(let_("x", int(1))),
+        println(call("identity", vec![int(2)])),
+        println(var("x")),
+    ]);
+
+    assert_eq!(output.unwrap(), "2\n1\n");
+}
@@ -0,0 +1,166 @@
+use runner::{
+    ast::{
+        ArithmeticOperator, Assignee, ComparisonOperation, ComparisonOperator, Expression,
+        ExpressionKind, FieldValue, HighLevelItem, StructLiteral,
+    },
+    mem::Value,
+    RuntimeError,
+};
+
+mod common;
+
+use common::{field, int, let_, point_struct, println, reassign, run, var};
+
+fn field_of<'a>(variable: &'a str, field: &'a str) -> Assignee<'a> {
+    Assignee::Field {
+        base: Box::new(Assignee::Variable(variable)),
+        field,
+    }
+}
+
+fn point<'a>(fields: Vec<(&'a str, Expression<'a>)>) -> Expression<'a> {
+    ExpressionKind::StructLiteral(StructLiteral {
+        name: "Point",
+        fields: fields
+            .into_iter()
+            .map(|(name, expression)| FieldValue { name, expression })
+            .collect(),
+    })
+    .into()
+}
+
+#[test]
+fn literals_and_fields() {
+    // Fields are kept in the order they were declared
+    let output = run(vec![
+        point_struct(),
+        HighLevelItem::Expr(let_("p", point(vec![("y", int(2)), ("x", int(1))]))),
+        println(var("p")),
+        println(field(var("p"), "y")),
+    ]);
+
+    assert_eq!(output.unwrap(), "Point { x: 1, y: 2 }\n2\n");
+}
+
+#[test]
+fn fields_are_checked_against_the_declaration() {
+    let output = run(vec![point_struct(), println(point(vec![("x", int(1))]))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::MissingField { ref field, .. }) if field == "y"
+    ));
+
+    let output = run(vec![
+        point_struct(),
+        println(point(vec![("x", int(1)), ("y", int(2)), ("z", int(3))])),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::UndefinedField { ref field, .. }) if field == "z"
+    ));
+
+    let output = run(vec![
+        point_struct(),
+        println(point(vec![("x", int(1)), ("x", int(2)), ("y", int(3))])),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::FieldRepeated(ref field)) if field == "x"
+    ));
+
+    let output = run(vec![
+        point_struct(),
+        println(field(point(vec![("x", int(1)), ("y", int(2))]), "z")),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::UndefinedField { ref struct_name, .. }) if struct_name == "Point"
+    ));
+
+    let output = run(vec![println(point(vec![]))]);
+    assert!(matches!(output, Err(RuntimeError::UndefinedType(ref name)) if name == "Point"));
+
+    let output = run(vec![point_struct(), point_struct()]);
+    assert!(matches!(output, Err(RuntimeError::TypeRedefined(_))));
+}
+
+#[test]
+fn field_writes() {
+    let output = run(vec![
+        point_struct(),
+        HighLevelItem::Expr(let_("p", point(vec![("x", int(1)), ("y", int(2))]))),
+        HighLevelItem::Expr(let_("copy", var("p"))),
+        HighLevelItem::Expr(reassign(field_of("p", "x"), None, int(10))),
+        HighLevelItem::Expr(reassign(
+            field_of("p", "y"),
+            Some(ArithmeticOperator::Multiply),
+            int(3),
+        )),
+        println(var("p")),
+        // Copies don't see the changes
+        println(var("copy")),
+    ]);
+
+    assert_eq!(
+        output.unwrap(),
+        "Point { x: 10, y: 6 }\nPoint { x: 1, y: 2 }\n"
+    );
+}
+
+#[test]
+fn field_writes_keep_the_type() {
+    let output = run(vec![
+        point_struct(),
+        HighLevelItem::Expr(let_("p", point(vec![("x", int(1)), ("y", int(2))]))),
+        HighLevelItem::Expr(reassign(
+            field_of("p", "x"),
+            None,
+            ExpressionKind::BooleanLiteral(true).into(),
+        )),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ReassignmentTypeMismatch {
+            ref name,
+            expected: "int",
+            found: Value::Boolean(true),
+        }) if name == "p.x"
+    ));
+
+    let output = run(vec![
+        HighLevelItem::Expr(let_("n", int(1))),
+        HighLevelItem::Expr(reassign(field_of("n", "x"), None, int(2))),
+    ]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::UnexpectedType {
+            expected: "struct",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn equality() {
+    let compare = |op, left, right| {
+        println(
+            ExpressionKind::CompOperation(Box::new(ComparisonOperation { left, op, right })).into(),
+        )
+    };
+
+    let output = run(vec![
+        point_struct(),
+        compare(
+            ComparisonOperator::Equals,
+            point(vec![("x", int(1)), ("y", int(2))]),
+            point(vec![("y", int(2)), ("x", int(1))]),
+        ),
+        compare(
+            ComparisonOperator::NotEquals,
+            point(vec![("x", int(1)), ("y", int(2))]),
+            point(vec![("x", int(1)), ("y", int(3))]),
+        ),
+    ]);
+
+    assert_eq!(output.unwrap(), "true\ntrue\n");
+}
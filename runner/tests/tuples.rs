@@ -0,0 +1,194 @@
+use runner::{
+    ast::{
+        Assignee, Assignment, ComparisonOperation, ComparisonOperator, Expression, ExpressionKind,
+        HighLevelItem, Index, MethodCall, Pattern,
+    },
+    mem::Value,
+    RuntimeError,
+};
+
+mod common;
+
+use common::{get, int, println, reassign, run, tuple, var};
+
+fn let_<'a>(pattern: Pattern<'a>, expression: Expression<'a>) -> HighLevelItem<'a> {
+    HighLevelItem::Expr(
+        ExpressionKind::Assignment(Box::new(Assignment {
+            pattern,
+            type_annotation: None,
+            expression,
+        }))
+        .into(),
+    )
+}
+
+#[test]
+fn literals_and_printing() {
+    let output = run(vec![
+        println(tuple(vec![int(10), int(20)])),
+        println(tuple(vec![
+            ExpressionKind::StringLiteral("one".into()).into()
+        ])),
+        println(tuple(vec![
+            tuple(vec![int(1), ExpressionKind::CharLiteral('c').into()]),
+            ExpressionKind::FloatLiteral(0.5).into(),
+        ])),
+    ]);
+
+    assert_eq!(
+        output.unwrap(),
+        "(10, 20)\n(\"one\",)\n((1, 'c'), 0.50000)\n"
+    );
+}
+
+#[test]
+fn destructuring() {
+    use Pattern::*;
+
+    let output = run(vec![
+        let_(
+            Tuple(vec![
+                Identifier("a"),
+                Tuple(vec![Identifier("b"), Identifier("c")]),
+            ]),
+            tuple(vec![int(1), tuple(vec![int(2), int(3)])]),
+        ),
+        println(var("a")),
+        println(var("b")),
+        println(var("c")),
+    ]);
+    assert_eq!(output.unwrap(), "1\n2\n3\n");
+
+    let output = run(vec![let_(
+        Tuple(vec![Identifier("a"), Identifier("b")]),
+        tuple(vec![int(1), int(2), int(3)]),
+    )]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::TupleLengthMismatch { expected: 2, .. })
+    ));
+
+    let output = run(vec![let_(
+        Tuple(vec![Identifier("a"), Identifier("b")]),
+        int(1),
+    )]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::UnexpectedType {
+            expected: "tuple",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn swapping() {
+    // The whole tuple is evaluated before any variable changes
+    let output = run(vec![
+        let_(Pattern::Identifier("a"), int(1)),
+        let_(Pattern::Identifier("b"), int(2)),
+        HighLevelItem::Expr(reassign(
+            Assignee::Tuple(vec![Assignee::Variable("a"), Assignee::Variable("b")]),
+            None,
+            tuple(vec![var("b"), var("a")]),
+        )),
+        println(var("a")),
+        println(var("b")),
+    ]);
+
+    assert_eq!(output.unwrap(), "2\n1\n");
+}
+
+#[test]
+fn indexing_and_comparisons() {
+    let output = run(vec![
+        let_(Pattern::Identifier("t"), tuple(vec![int(10), int(20)])),
+        println(
+            ExpressionKind::Index(Box::new(Index {
+                indexed: var("t"),
+                index: int(-1),
+            }))
+            .into(),
+        ),
+        println(
+            ExpressionKind::CompOperation(Box::new(ComparisonOperation {
+                left: var("t"),
+                op: ComparisonOperator::Equals,
+                right: tuple(vec![int(10), int(20)]),
+            }))
+            .into(),
+        ),
+        println(
+            ExpressionKind::CompOperation(Box::new(ComparisonOperation {
+                left: var("t"),
+                op: ComparisonOperator::Less,
+                right: tuple(vec![int(10), int(30)]),
+            }))
+            .into(),
+        ),
+    ]);
+
+    assert_eq!(output.unwrap(), "20\ntrue\ntrue\n");
+}
+
+#[test]
+fn get_returns_an_option() {
+    let four = || tuple(vec![int(1), int(2), int(3), int(4)]);
+    let output = run(vec![
+        println(get(four(), 0)),
+        println(get(four(), 3)),
+        println(get(four(), 4)),
+        println(get(four(), -1)),
+        println(get(four(), -4)),
+        println(get(four(), -5)),
+        println(get(four(), i64::MIN)),
+    ]);
+
+    assert_eq!(
+        output.unwrap(),
+        "Some(1)\nSome(4)\nNone\nSome(4)\nSome(1)\nNone\nNone\n"
+    );
+
+    let output = run(vec![println(get(int(1), 0))]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::UndefinedMethod { ref type_name, ref method })
+            if type_name == "int" && method == "get"
+    ));
+}
+
+#[test]
+fn get_type_errors() {
+    let output = run(vec![println(
+        ExpressionKind::MethodCall(Box::new(MethodCall {
+            receiver: tuple(vec![int(1)]),
+            name: "get",
+            params: vec![],
+        }))
+        .into(),
+    )]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::ArityMismatch {
+            expected: 1,
+            found: 0,
+            ..
+        })
+    ));
+
+    let output = run(vec![println(
+        ExpressionKind::MethodCall(Box::new(MethodCall {
+            receiver: tuple(vec![int(1)]),
+            name: "get",
+            params: vec![ExpressionKind::BooleanLiteral(true).into()],
+        }))
+        .into(),
+    )]);
+    assert!(matches!(
+        output,
+        Err(RuntimeError::UnexpectedType {
+            expected: "int",
+            found: Value::Boolean(true),
+        })
+    ));
+}